@@ -0,0 +1,112 @@
+//! Parsing for `chek::ordered!`.
+//!
+//! Rust (and so `syn`) refuses to parse `a <= b < c`, since comparison
+//! operators can't be chained. Instead, we split the tokens at the top-level
+//! comparison operators ourselves, and parse each operand separately.
+
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use syn::{BinOp, Expr};
+
+/// A comparison operator that may appear in a chain.
+pub(crate) struct Link {
+    pub(crate) op: TokenStream,
+    pub(crate) text: &'static str,
+}
+
+/// `a <= b < c`, as the operands `[a, b, c]` and the operators between them.
+pub(crate) struct Chain {
+    pub(crate) operands: Vec<Expr>,
+    pub(crate) links: Vec<Link>,
+}
+
+/// Splits `tokens` into a chain of comparisons, followed by an optional message
+/// after the first top-level comma.
+pub(crate) fn parse(tokens: TokenStream) -> syn::Result<(Chain, Option<TokenStream>)> {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let mut segments = vec![TokenStream::new()];
+    let mut links = Vec::new();
+    let mut msg = None;
+    // Depth of `<...>` nesting inside a turbofish, where `<` and `>` aren't
+    // comparisons.
+    let mut generic_depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        let op = punct_op(&tokens[i..]);
+        let len = op.chars().count().max(1);
+        let after_path_sep = i >= 2 && punct_op(&tokens[i - 2..]) == "::";
+        let comparison = match op {
+            "<" if generic_depth > 0 || after_path_sep => {
+                generic_depth += 1;
+                None
+            }
+            ">" | ">>" if generic_depth > 0 => {
+                generic_depth = generic_depth.saturating_sub(len);
+                None
+            }
+            "," if generic_depth == 0 => {
+                msg = Some(tokens[i + 1..].iter().cloned().collect::<TokenStream>()).filter(|m| !m.is_empty());
+                break;
+            }
+            "<" | "<=" | ">" | ">=" | "==" if generic_depth == 0 => Some(op),
+            _ => None,
+        };
+        match comparison {
+            Some(text) => {
+                links.push(Link { op: tokens[i..i + len].iter().cloned().collect(), text });
+                segments.push(TokenStream::new());
+            }
+            None => segments.last_mut().unwrap().extend(tokens[i..i + len].iter().cloned()),
+        }
+        i += len;
+    }
+    if links.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected a chain of comparisons, like `a <= b < c`",
+        ));
+    }
+    let operands = segments.into_iter().map(parse_operand).collect::<syn::Result<_>>()?;
+    Ok((Chain { operands, links }, msg))
+}
+
+fn parse_operand(tokens: TokenStream) -> syn::Result<Expr> {
+    if tokens.is_empty() {
+        return Err(syn::Error::new(proc_macro2::Span::call_site(), "missing operand in comparison chain"));
+    }
+    let expr: Expr = syn::parse2(tokens)?;
+    // These bind more loosely than comparisons, so `a && b < c` would mean
+    // something different here than it does in normal Rust.
+    let loose = match &expr {
+        Expr::Binary(bin) => matches!(bin.op, BinOp::And(_) | BinOp::Or(_)),
+        Expr::Range(_) | Expr::Assign(_) | Expr::Closure(_) => true,
+        _ => false,
+    };
+    if loose {
+        return Err(syn::Error::new_spanned(&expr, "operands of a comparison chain must be parenthesized"));
+    }
+    Ok(expr)
+}
+
+/// Returns the operator starting at the first token, if it's a punctuation
+/// character, using the same longest-match rule as the lexer. Returns `""` for
+/// anything else.
+fn punct_op(tokens: &[TokenTree]) -> &'static str {
+    const OPS: &[&str] = &[
+        "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>", "+=", "-=",
+        "*=", "/=", "%=", "^=", "&=", "|=", "..", "<", ">", "=", ",", ":", "!", "-", "+", "*", "/", "%", "^", "&",
+        "|", ".", ";", "#", "$", "?", "@", "~",
+    ];
+    let mut chars = String::new();
+    for token in tokens.iter().take(3) {
+        match token {
+            TokenTree::Punct(p) => {
+                chars.push(p.as_char());
+                if p.spacing() == Spacing::Alone {
+                    break;
+                }
+            }
+            _ => break,
+        }
+    }
+    OPS.iter().find(|op| chars.starts_with(**op)).copied().unwrap_or("")
+}
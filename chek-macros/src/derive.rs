@@ -0,0 +1,59 @@
+//! `#[derive(AlmostEq)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{parse_quote, Data, DeriveInput, GenericParam, Ident, Index};
+
+/// Implements `chek::AlmostEq` for a struct by comparing each field in order,
+/// adding the field's name to the path of the first one that differs.
+pub(crate) fn almost_eq(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Err(syn::Error::new_spanned(data.enum_token, "`AlmostEq` can only be derived for structs"));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(data.union_token, "`AlmostEq` can only be derived for structs"));
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in &mut generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds.push(parse_quote!(::chek::AlmostEq));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let checks = fields.iter().enumerate().map(|(i, field)| {
+        let (member, name) = match &field.ident {
+            Some(ident) => {
+                let name = ident.to_string();
+                (quote!(#ident), name.trim_start_matches("r#").to_string())
+            }
+            None => {
+                let index = Index::from(i);
+                (quote!(#index), i.to_string())
+            }
+        };
+        quote! {
+            if let ::core::option::Option::Some(mismatch) = ::chek::AlmostEq::first_mismatch(&self.#member, &other.#member) {
+                return ::core::option::Option::Some(mismatch.in_field(#name));
+            }
+        }
+    });
+    // Unit structs don't look at `other`.
+    let other = Ident::new(if fields.is_empty() { "_other" } else { "other" }, Span::call_site());
+    let name = &input.ident;
+    Ok(quote! {
+        impl #impl_generics ::chek::AlmostEq for #name #ty_generics #where_clause {
+            fn first_mismatch<'__chek_a>(
+                &'__chek_a self,
+                #other: &'__chek_a Self,
+            ) -> ::core::option::Option<::chek::Mismatch<'__chek_a>> {
+                #(#checks)*
+                ::core::option::Option::None
+            }
+        }
+    })
+}
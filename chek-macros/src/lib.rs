@@ -0,0 +1,466 @@
+//! Procedural macros used by [`chek`](https://docs.rs/chek).
+//!
+//! Everything in here is an implementation detail of `chek`, and is only
+//! usable through the wrapper macros and derives it exports (e.g.
+//! `chek::check!`). Don't depend on this crate directly.
+
+extern crate proc_macro;
+
+mod chain;
+mod derive;
+mod source;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{BinOp, Expr, Ident, LitStr, Token, UnOp};
+
+/// Input to `__check!`, which is always invoked by `chek::check!` (or one of
+/// its variants) as `__check!($crate, "name", expr, optional message...)`. The
+/// `try_` variants put `try` before the name, to get a `Result` rather than a
+/// panic.
+struct CheckInput {
+    krate: TokenTree,
+    try_: bool,
+    which: LitStr,
+    expr: Expr,
+    msg: Option<TokenStream2>,
+}
+
+impl Parse for CheckInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let try_ = input.parse::<Option<Token![try]>>()?.is_some();
+        let which = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let expr = input.parse()?;
+        let mut msg = None;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if !input.is_empty() {
+                msg = Some(input.parse()?);
+            }
+        }
+        Ok(CheckInput { krate, try_, which, expr, msg })
+    }
+}
+
+/// Rewrites a boolean expression so that the value of each interesting
+/// subexpression gets stashed in a local "slot" as it's evaluated, without
+/// changing evaluation order or short-circuiting.
+struct Instrumenter<'a> {
+    krate: &'a TokenTree,
+    /// Each slot, the subexpression it records, and whether it's filled by
+    /// `CopyValue::record` rather than `check_store`.
+    slots: Vec<(Ident, Expr, bool)>,
+}
+
+impl<'a> Instrumenter<'a> {
+    fn new_slot(&mut self, expr: &Expr) -> Ident {
+        let ident = format_ident!("__chek_v{}", self.slots.len(), span = Span::mixed_site());
+        self.slots.push((ident.clone(), expr.clone(), false));
+        ident
+    }
+
+    /// Like `new_slot`, but for a value that's recorded with `copy` rather than
+    /// moved into the slot.
+    fn new_copy_slot(&mut self, expr: &Expr) -> Ident {
+        let ident = self.new_slot(expr);
+        self.slots.last_mut().unwrap().2 = true;
+        ident
+    }
+
+    /// A statement that records a copy of the value of `place` (if it's `Copy`)
+    /// in a new slot.
+    fn copy(&mut self, label: &Expr, place: TokenStream2) -> TokenStream2 {
+        let krate = self.krate;
+        let slot = self.new_copy_slot(label);
+        quote!((&&#krate::__internal::CheckCopy(&#place)).record(&mut #slot);)
+    }
+
+    /// Instrument an expression in a position where it must produce a `bool`.
+    fn condition(&mut self, expr: &Expr) -> TokenStream2 {
+        match expr {
+            Expr::Binary(bin) => match bin.op {
+                BinOp::And(_) | BinOp::Or(_) => {
+                    let (left, right) = (self.condition(&bin.left), self.condition(&bin.right));
+                    let op = &bin.op;
+                    quote!((#left #op #right))
+                }
+                BinOp::Lt(_) | BinOp::Le(_) | BinOp::Gt(_) | BinOp::Ge(_) | BinOp::Eq(_) | BinOp::Ne(_) => {
+                    let (left, right) = (self.operand(&bin.left), self.operand(&bin.right));
+                    let op = &bin.op;
+                    quote!((#left #op #right))
+                }
+                _ => self.leaf(expr),
+            },
+            Expr::Unary(un) if matches!(un.op, UnOp::Not(_)) => {
+                let inner = self.condition(&un.expr);
+                quote!((!#inner))
+            }
+            Expr::Paren(paren) => self.condition(&paren.expr),
+            Expr::Group(group) => self.condition(&group.expr),
+            // Not much point in printing `true = true`.
+            Expr::Lit(_) => quote!((#expr)),
+            _ => self.leaf(expr),
+        }
+    }
+
+    /// A boolean that we can't break down any further, e.g. `v.is_empty()`.
+    fn leaf(&mut self, expr: &Expr) -> TokenStream2 {
+        let krate = self.krate;
+        let value = self.parts(expr);
+        let slot = self.new_slot(expr);
+        quote!((*#krate::__internal::check_store(&mut #slot, #value)))
+    }
+
+    /// Instrument one side of a comparison. This produces a reference to the
+    /// value, since that's what the comparison operators operate on anyway.
+    fn operand(&mut self, expr: &Expr) -> TokenStream2 {
+        let krate = self.krate;
+        match expr {
+            Expr::Paren(paren) => self.operand(&paren.expr),
+            Expr::Group(group) => self.operand(&group.expr),
+            Expr::Lit(_) => quote!((&(#expr))),
+            _ if is_place(expr) => {
+                let slot = self.new_slot(expr);
+                quote!((*#krate::__internal::check_store(&mut #slot, &(#expr))))
+            }
+            _ if can_move(expr) => {
+                let value = self.parts(expr);
+                let slot = self.new_slot(expr);
+                quote!((#krate::__internal::check_store(&mut #slot, #value)))
+            }
+            _ => quote!((&(#expr))),
+        }
+    }
+
+    /// Instrument the receiver and arguments of a method call, the arguments of
+    /// a function call, or the operands of an arithmetic operator. The value of
+    /// the expression itself is left to the caller.
+    fn parts(&mut self, expr: &Expr) -> TokenStream2 {
+        match expr {
+            Expr::MethodCall(call) => {
+                let mut records = Vec::new();
+                let receiver = self.part(&call.receiver, &mut records);
+                let args: Vec<_> = call.args.iter().map(|arg| self.part(arg, &mut records)).collect();
+                let (method, turbofish) = (&call.method, &call.turbofish);
+                quote!({ #(#records)* (#receiver).#method #turbofish(#(#args),*) })
+            }
+            Expr::Call(call) => {
+                let mut records = Vec::new();
+                let args: Vec<_> = call.args.iter().map(|arg| self.part(arg, &mut records)).collect();
+                let func = &call.func;
+                quote!({ #(#records)* #func(#(#args),*) })
+            }
+            Expr::Binary(bin) if is_arithmetic(&bin.op) => {
+                let (left, right) = (self.term(&bin.left), self.term(&bin.right));
+                let op = &bin.op;
+                quote!((#left #op #right))
+            }
+            Expr::Paren(paren) => {
+                let inner = self.parts(&paren.expr);
+                quote!((#inner))
+            }
+            _ => quote!(#expr),
+        }
+    }
+
+    /// Instrument a receiver or argument. Places (and references to them, like
+    /// `&x`) are recorded by `records`, which run before the call, so that the
+    /// receiver or argument itself is passed along untouched. So are the
+    /// results of arithmetic, as in `term`. Other values aren't recorded, since
+    /// we'd have to move them, and that would get in the way of inferring
+    /// their type (e.g. for `x.into()`), but their parts are.
+    fn part(&mut self, expr: &Expr, records: &mut Vec<TokenStream2>) -> TokenStream2 {
+        match expr {
+            Expr::Reference(r) if r.mutability.is_none() && is_place(&r.expr) => {
+                records.push(self.copy(&r.expr, quote!(#expr)));
+                quote!(#expr)
+            }
+            _ if is_place(expr) => {
+                records.push(self.copy(expr, quote!((#expr))));
+                quote!(#expr)
+            }
+            Expr::Binary(bin) if is_arithmetic(&bin.op) => self.term(expr),
+            _ => self.parts(expr),
+        }
+    }
+
+    /// Instrument an operand of an arithmetic operator, which is recorded
+    /// (if it's `Copy`) as well as its parts. Operators take their operands
+    /// by value anyway, so moving it through a `match` doesn't change anything.
+    fn term(&mut self, expr: &Expr) -> TokenStream2 {
+        match expr {
+            Expr::Paren(paren) => self.term(&paren.expr),
+            Expr::Group(group) => self.term(&group.expr),
+            Expr::Lit(_) => quote!((#expr)),
+            _ => {
+                let value = self.parts(expr);
+                let tmp = Ident::new("__chek_value", Span::mixed_site());
+                let record = self.copy(expr, quote!(#tmp));
+                quote!((match #value { #tmp => { #record #tmp } }))
+            }
+        }
+    }
+}
+
+/// Returns true for the operators that `Instrumenter::parts` breaks down.
+fn is_arithmetic(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::Add(_)
+            | BinOp::Sub(_)
+            | BinOp::Mul(_)
+            | BinOp::Div(_)
+            | BinOp::Rem(_)
+            | BinOp::BitXor(_)
+            | BinOp::BitAnd(_)
+            | BinOp::BitOr(_)
+            | BinOp::Shl(_)
+            | BinOp::Shr(_)
+    )
+}
+
+/// Returns true for expressions we can borrow without creating a temporary,
+/// e.g. `foo`, `foo.bar`, `foo[3]`. We only need to get this right in the
+/// conservative direction -- things we answer `false` for just get moved
+/// into their slot instead.
+fn is_place(expr: &Expr) -> bool {
+    match expr {
+        // Heuristic: `foo` is probably a local, but `Foo`, `FOO`, and
+        // `foo::Bar` are probably constants, unit structs or enum variants,
+        // which borrowing would produce a temporary for.
+        Expr::Path(path) => {
+            path.qself.is_none()
+                && path.path.leading_colon.is_none()
+                && path.path.segments.len() == 1
+                && path.path.segments[0].arguments.is_none()
+                && !path.path.segments[0].ident.to_string().starts_with(|c: char| c.is_uppercase())
+        }
+        Expr::Field(field) => is_place(&field.base),
+        Expr::Index(index) => is_place(&index.expr),
+        Expr::Paren(paren) => is_place(&paren.expr),
+        Expr::Group(group) => is_place(&group.expr),
+        Expr::Unary(un) if matches!(un.op, UnOp::Deref(_)) => is_place(&un.expr),
+        _ => false,
+    }
+}
+
+/// Returns false for expressions where moving the value into a slot would
+/// either fail or dangle, e.g. `*make_box()` or `&make_value()`. These just
+/// don't get reported.
+fn can_move(expr: &Expr) -> bool {
+    match expr {
+        Expr::Unary(un) if matches!(un.op, UnOp::Deref(_)) => false,
+        Expr::Reference(r) => is_place(&r.expr),
+        _ => true,
+    }
+}
+
+/// Implementation of `chek::check!` and friends. Not public API.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __check(input: TokenStream) -> TokenStream {
+    let CheckInput { krate, try_, which, expr, msg } = syn::parse_macro_input!(input as CheckInput);
+    let mut inst = Instrumenter { krate: &krate, slots: Vec::new() };
+    let condition = inst.condition(&expr);
+
+    let slot_decls = inst.slots.iter().map(|(slot, _, _)| {
+        quote!(#[allow(unused_mut)] let mut #slot = ::core::option::Option::None;)
+    });
+    let values = inst.slots.iter().map(|(slot, sub, copied)| {
+        let sub_str = source::to_source(quote!(#sub));
+        match copied {
+            true => quote!((#sub_str, #krate::__internal::check_copied(&#slot))),
+            false => quote!((#sub_str, #krate::__internal::check_slot(&#slot))),
+        }
+    });
+    let imports = quote! {
+        #[allow(unused_imports)]
+        use #krate::__internal::{CopyValue as _, NoCopyValue as _};
+    };
+    let expr_str = source::to_source(quote!(#expr));
+
+    if try_ {
+        let msg = match msg {
+            Some(msg) => quote!(::core::option::Option::Some(::core::format_args!(#msg))),
+            None => quote!(::core::option::Option::None),
+        };
+        // The result is bound to a local so that the temporaries borrowing the
+        // slots are dropped before the slots are.
+        let result = Ident::new("__chek_result", Span::mixed_site());
+        return quote!({
+            #imports
+            #(#slot_decls)*
+            let #result = match #condition {
+                true => ::core::result::Result::Ok(()),
+                false => ::core::result::Result::Err(#krate::__internal::check_failure(#which, #expr_str, &[#(#values),*], #msg)),
+            };
+            #result
+        })
+        .into();
+    }
+
+    let msg = msg.map(|msg| quote!(, ::core::format_args!(#msg)));
+
+    // This uses `match` rather than `if` so that temporaries created while
+    // evaluating the condition live until we're done reporting the failure.
+    quote!({
+        #imports
+        #(#slot_decls)*
+        match #condition {
+            true => {}
+            false => {
+                #krate::__check_fail!(#which, #expr_str, &[#(#values),*] #msg);
+            }
+        }
+    })
+    .into()
+}
+
+/// What `__ordered!` produces when the chain doesn't hold.
+enum Mode {
+    /// Panic (or call the failure handler), for `chek::ordered!`.
+    Panic,
+    /// Evaluate to `Err(chek::Failure)`, for `chek::try_ordered!`.
+    Try,
+    /// Return `Err` from the enclosing function, for `chek::ensure_ordered!`.
+    /// Anything after the chain is the error rather than a message.
+    Ensure,
+}
+
+/// Input to `__ordered!`, which is always invoked by `chek::ordered!` (or one
+/// of its variants) as `__ordered!($crate, "name", chain, optional message...)`.
+/// The `try_` and `ensure_` variants put `try` or `ensure` before the name.
+struct OrderedInput {
+    krate: TokenTree,
+    mode: Mode,
+    which: LitStr,
+    rest: TokenStream2,
+}
+
+impl Parse for OrderedInput {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let mode = if input.parse::<Option<Token![try]>>()?.is_some() {
+            Mode::Try
+        } else if input.peek(Ident) {
+            let ident: Ident = input.parse()?;
+            if ident != "ensure" {
+                return Err(syn::Error::new(ident.span(), "expected `try` or `ensure`"));
+            }
+            Mode::Ensure
+        } else {
+            Mode::Panic
+        };
+        let which = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let rest = input.parse()?;
+        Ok(OrderedInput { krate, mode, which, rest })
+    }
+}
+
+/// Implementation of `chek::ordered!` and friends. Not public API.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __ordered(input: TokenStream) -> TokenStream {
+    let OrderedInput { krate, mode, which, rest } = syn::parse_macro_input!(input as OrderedInput);
+    let (chain, msg) = match chain::parse(rest) {
+        Ok(parsed) => parsed,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let slots: Vec<Ident> = (0..chain.operands.len())
+        .map(|i| format_ident!("__chek_v{}", i, span = Span::mixed_site()))
+        .collect();
+    let operand_strs: Vec<String> = chain.operands.iter().map(|e| source::to_source(quote!(#e))).collect();
+    let operands = &chain.operands;
+    let values = slots.iter().zip(&operand_strs).map(|(slot, s)| quote!((#s, #slot as &dyn ::core::fmt::Debug)));
+    let values = quote!(&[#(#values),*]);
+    let mut expr_str = operand_strs[0].clone();
+    for (link, operand) in chain.links.iter().zip(&operand_strs[1..]) {
+        expr_str = format!("{} {} {}", expr_str, link.text, operand);
+    }
+    let link_strs = chain.links.iter().enumerate().map(|(i, link)| {
+        format!("{} {} {}", operand_strs[i], link.text, operand_strs[i + 1])
+    });
+    let conds = chain.links.iter().enumerate().map(|(i, link)| {
+        let (left, right, op) = (&slots[i], &slots[i + 1], &link.op);
+        quote!(!(#left #op #right))
+    });
+
+    if let Mode::Panic = mode {
+        let msg = msg.map(|msg| quote!(, ::core::format_args!(#msg)));
+        // Check each link in turn, reporting the first one that doesn't hold.
+        let checks = conds.zip(link_strs).map(|(cond, link_str)| {
+            quote!(if #cond {
+                #krate::__chain_fail!(#which, #expr_str, #values, #link_str #msg);
+            })
+        });
+        return quote!({
+            #(let #slots = &(#operands);)*
+            #(#checks else)* {}
+        })
+        .into();
+    }
+
+    let (msg, err) = match mode {
+        Mode::Try => (msg, None),
+        _ => (None, msg.map(strip_trailing_comma)),
+    };
+    let msg = match msg {
+        Some(msg) => quote!(::core::option::Option::Some(::core::format_args!(#msg))),
+        None => quote!(::core::option::Option::None),
+    };
+    let checks = conds.zip(link_strs).map(|(cond, link_str)| {
+        quote!(if #cond {
+            ::core::result::Result::Err(#krate::__internal::chain_failure(#which, #expr_str, #values, #link_str, #msg))
+        })
+    });
+    let result = Ident::new("__chek_result", Span::mixed_site());
+    let result = quote!({
+        #(let #slots = &(#operands);)*
+        let #result = #(#checks else)* { ::core::result::Result::Ok(()) };
+        #result
+    });
+    if let Mode::Try = mode {
+        return result.into();
+    }
+
+    let failure = Ident::new("__chek_failure", Span::mixed_site());
+    let error = match err {
+        Some(err) => quote!(#krate::__ensure_error!(#err, #failure)),
+        None => quote!(#failure),
+    };
+    quote!({
+        if let ::core::result::Result::Err(#failure) = #result {
+            return ::core::result::Result::Err(::core::convert::From::from(#error));
+        }
+    })
+    .into()
+}
+
+/// Removes the optional trailing comma after the error in
+/// `chek::ensure_ordered!(a < b, error,)`, since it's not a message anymore.
+fn strip_trailing_comma(tokens: TokenStream2) -> TokenStream2 {
+    let mut tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    if matches!(tokens.last(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+        tokens.pop();
+    }
+    tokens.into_iter().collect()
+}
+
+/// Derives `chek::AlmostEq` for a struct whose fields all implement it. See
+/// `chek::AlmostEq` for details.
+#[proc_macro_derive(AlmostEq)]
+pub fn derive_almost_eq(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match derive::almost_eq(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,167 @@
+//! Turns tokens back into source text for failure messages.
+//!
+//! This would just be `stringify!`, except that tokens which have been through
+//! a procedural macro lose the information rustc uses to decide where spaces
+//! go, so it prints things like `! (a < b)` and `v.iter().any(| x | x > 1)`.
+//! Instead, we approximate the output of rustc's pretty printer here.
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+
+/// What came before the current token, as far as spacing is concerned.
+#[derive(Clone, Copy, PartialEq)]
+enum Prev {
+    /// Start of a stream.
+    Start,
+    /// The end of an operand, e.g. an identifier, literal or group. A `-` or
+    /// `&` after one of these is a binary operator.
+    Operand,
+    /// An identifier that's a keyword like `return` or `move`. Needs a space
+    /// after it, but a `-` or `&` after it is a prefix operator.
+    Keyword,
+    /// Something that wants a space after it, e.g. `,` or a binary operator.
+    Spaced,
+    /// Something that binds to what follows it, e.g. `.`, `::`, prefix `!`.
+    Glued,
+    /// The `!` of a macro invocation.
+    MacroBang,
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "box", "break", "dyn", "else", "for", "if", "impl", "in", "let", "match", "move", "mut", "ref",
+    "return", "while", "yield",
+];
+
+struct Printer {
+    out: String,
+    prev: Prev,
+    /// True when we're inside a closure's `|...|` parameter list.
+    in_closure_params: bool,
+    /// Depth of `<...>` nesting inside a turbofish.
+    generic_depth: usize,
+}
+
+pub(crate) fn to_source(tokens: TokenStream) -> String {
+    let mut printer = Printer::new();
+    printer.stream(tokens);
+    printer.out
+}
+
+impl Printer {
+    fn new() -> Self {
+        Printer { out: String::new(), prev: Prev::Start, in_closure_params: false, generic_depth: 0 }
+    }
+
+    fn space(&mut self) {
+        if !matches!(self.prev, Prev::Start | Prev::Glued | Prev::MacroBang) {
+            self.out.push(' ');
+        }
+    }
+
+    fn is_prefix_position(&self) -> bool {
+        matches!(self.prev, Prev::Start | Prev::Keyword | Prev::Spaced | Prev::Glued)
+    }
+
+    fn stream(&mut self, tokens: TokenStream) {
+        let mut iter = tokens.into_iter().peekable();
+        while let Some(tt) = iter.next() {
+            match tt {
+                TokenTree::Group(group) => {
+                    let inner = || {
+                        let mut p = Printer::new();
+                        p.stream(group.stream());
+                        p.out
+                    };
+                    match group.delimiter() {
+                        Delimiter::None => self.stream(group.stream()),
+                        Delimiter::Brace => {
+                            self.space();
+                            let inner = inner();
+                            if inner.is_empty() {
+                                self.out.push_str("{}");
+                            } else {
+                                self.out.push_str("{ ");
+                                self.out.push_str(&inner);
+                                self.out.push_str(" }");
+                            }
+                            self.prev = Prev::Operand;
+                        }
+                        delim => {
+                            // Calls, indexing and macro invocations bind to
+                            // what comes before them.
+                            if !matches!(self.prev, Prev::Operand) {
+                                self.space();
+                            }
+                            let (open, close) = if delim == Delimiter::Parenthesis { ('(', ')') } else { ('[', ']') };
+                            self.out.push(open);
+                            self.out.push_str(&inner());
+                            self.out.push(close);
+                            self.prev = Prev::Operand;
+                        }
+                    }
+                }
+                TokenTree::Ident(ident) => {
+                    let s = ident.to_string();
+                    self.space();
+                    self.out.push_str(&s);
+                    self.prev = if KEYWORDS.contains(&s.as_str()) { Prev::Keyword } else { Prev::Operand };
+                }
+                TokenTree::Literal(lit) => {
+                    self.space();
+                    self.out.push_str(&lit.to_string());
+                    self.prev = Prev::Operand;
+                }
+                TokenTree::Punct(punct) => {
+                    let mut op = punct.as_char().to_string();
+                    let mut spacing = punct.spacing();
+                    while spacing == Spacing::Joint {
+                        match iter.peek() {
+                            Some(TokenTree::Punct(next)) => {
+                                op.push(next.as_char());
+                                spacing = next.spacing();
+                                iter.next();
+                            }
+                            // A lifetime, e.g. `'a`.
+                            _ => break,
+                        }
+                    }
+                    self.punct(&op);
+                }
+            }
+        }
+    }
+
+    fn punct(&mut self, op: &str) {
+        let prefix = self.is_prefix_position();
+        let (space_before, next) = match op {
+            "." | "::" | ".." | "..=" => (false, Prev::Glued),
+            "," | ";" | ":" => (false, Prev::Spaced),
+            "?" => (false, Prev::Operand),
+            "'" => (true, Prev::Glued),
+            "!" if self.prev == Prev::Operand => (false, Prev::MacroBang),
+            "!" | "-" | "*" | "&" | "&&" if prefix => (true, Prev::Glued),
+            "||" if prefix => (true, Prev::Spaced),
+            "|" if prefix && !self.in_closure_params => {
+                self.in_closure_params = true;
+                (true, Prev::Glued)
+            }
+            "|" if self.in_closure_params => {
+                self.in_closure_params = false;
+                (false, Prev::Spaced)
+            }
+            "<" if (self.prev == Prev::Glued && self.out.ends_with("::")) || self.generic_depth > 0 => {
+                self.generic_depth += 1;
+                (false, Prev::Glued)
+            }
+            ">" | ">>" if self.generic_depth > 0 => {
+                self.generic_depth = self.generic_depth.saturating_sub(op.len());
+                (false, Prev::Operand)
+            }
+            _ => (true, Prev::Spaced),
+        };
+        if space_before {
+            self.space();
+        }
+        self.out.push_str(op);
+        self.prev = next;
+    }
+}
@@ -0,0 +1,55 @@
+//! A sample binary used by `tests/size.rs` to measure how much code the
+//! assertions generate, with and without the `inline_panics` feature.
+//!
+//! It asserts on a bunch of different types, since that's what caused the
+//! out-of-line failure functions to get monomorphized over and over before
+//! they took `&dyn Debug`.
+
+use std::hint::black_box;
+
+macro_rules! exercise {
+    ($($name:ident: $t:ty = $v:expr;)+) => {
+        $(
+            #[inline(never)]
+            fn $name(a: $t, b: $t) {
+                chek::lt!(a, b);
+                chek::le!(a, b, "with a message: {:?}", a);
+                chek::gt!(b, a);
+                chek::ge!(b, a, "with a message");
+                chek::ne!(a, b);
+                chek::eq!(a, a, "with a message: {:?}", b);
+            }
+        )+
+
+        fn main() {
+            // Most of these assertions fail, but it doesn't matter, since we
+            // never actually run them. `black_box` keeps them from being
+            // optimized out.
+            if black_box(false) {
+                $(
+                    let v: $t = $v;
+                    $name(black_box(v.clone()), black_box(v));
+                )+
+            }
+        }
+    };
+}
+
+exercise! {
+    unsigned8: u8 = 1;
+    unsigned16: u16 = 1;
+    unsigned32: u32 = 1;
+    unsigned64: u64 = 1;
+    signed8: i8 = 1;
+    signed16: i16 = 1;
+    signed32: i32 = 1;
+    signed64: i64 = 1;
+    float32: f32 = 1.0;
+    float64: f64 = 1.0;
+    character: char = 'a';
+    string: String = "a".to_string();
+    strs: &'static str = "a";
+    bytes: Vec<u8> = vec![1];
+    pair: (u8, i64) = (1, 2);
+    option: Option<u32> = Some(1);
+}
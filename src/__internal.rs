@@ -1,51 +1,537 @@
+use core::cmp::Ordering;
+use core::fmt::{Arguments, Debug, Display};
+use core::ops::{Bound, RangeBounds};
+
+// When an assertion fails, it calls one of the `__*_fail!` macros below. These
+// hand the failure to the handler from `chek::set_failure_handler` if there is
+// one, and panic otherwise.
+//
+// Without `inline_panics`, that all happens in a `#[cold]` function which takes
+// the values as `&dyn Debug`, so that there's only one copy of it regardless of
+// how many types get asserted on, and which is `#[track_caller]` so that the
+// panic (and the `Failure`) report the location of the assertion, rather than a
+// location in this file.
+
+#[doc(hidden)]
+#[inline]
+pub fn failure_handler() -> Option<crate::FailureHandler> {
+    crate::handler::failure_handler()
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn cmp_failure(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (left, left_str), Some((right, right_str)), msg, None)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn eq_failure(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (left, left_str), Some((right, right_str)), msg, Some(&EqDiff(left, right)))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn ord_failure(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    ord: Option<Ordering>,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (left, left_str), Some((right, right_str)), msg, Some(&OrdOutcome(ord)))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn range_failure(
+    which: &'static str,
+    value: &dyn Debug,
+    range: &dyn Debug,
+    value_str: &'static str,
+    range_str: &'static str,
+    outcome: RangeOutcome,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (value, value_str), Some((range, range_str)), msg, Some(&outcome))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn contains_failure(
+    which: &'static str,
+    haystack: &dyn Debug,
+    needle: &dyn Debug,
+    haystack_str: &'static str,
+    needle_str: &'static str,
+    outcome: ContainsOutcome<'_>,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (&Preview(haystack), haystack_str), Some((needle, needle_str)), msg, Some(&outcome))
+}
+
 #[cold]
 #[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn slice_failure(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    mismatch: SliceMismatch,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (left, left_str), Some((right, right_str)), msg, Some(&mismatch))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn bytes_failure(
+    which: &'static str,
+    left: &[u8],
+    right: &[u8],
+    left_str: &'static str,
+    right_str: &'static str,
+    mismatch: BytesMismatch,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    let (left_len, right_len) = (ByteCount(left.len()), ByteCount(right.len()));
+    crate::Failure::new(which, (&left_len, left_str), Some((&right_len, right_str)), msg, Some(&HexDiff(left, right, mismatch)))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn details_failure(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    details: &dyn Display,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (left, left_str), Some((right, right_str)), msg, Some(details))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn value_failure(which: &'static str, value: &dyn Debug, value_str: &'static str, msg: Option<Arguments<'_>>) -> crate::Failure {
+    crate::Failure::new(which, (value, value_str), None, msg, None)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn value_details_failure(
+    which: &'static str,
+    value: &dyn Debug,
+    value_str: &'static str,
+    details: &dyn Display,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::new(which, (value, value_str), None, msg, Some(details))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn check_failure(
+    which: &'static str,
+    expr_str: &'static str,
+    values: &[(&str, CheckValue<'_>)],
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::expr(which, expr_str, msg, &CheckValues(values))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn chain_failure(
+    which: &'static str,
+    expr_str: &'static str,
+    values: &[(&str, &dyn Debug)],
+    link_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::expr(which, expr_str, msg, &ChainValues(values, link_str))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn sorted_failure(
+    which: &'static str,
+    expr_str: &'static str,
+    index: usize,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::expr(which, expr_str, msg, &UnsortedPair(index, left, right))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn match_failure(
+    which: &'static str,
+    value: &dyn Debug,
+    value_str: &'static str,
+    pat_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) -> crate::Failure {
+    crate::Failure::pattern(which, (value, value_str), pat_str, msg)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
 #[doc(hidden)]
 #[cfg(not(feature = "inline_panics"))]
-pub fn cmp_assert_fail<A, B>(which: &str, left: A, right: B, left_str: &str, right_str: &str) -> !
-where
-    A: core::fmt::Debug,
-    B: core::fmt::Debug,
-{
-    panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`", which, left, left_str, right, right_str);
+pub fn cmp_assert_fail(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&cmp_failure(which, left, right, left_str, right_str, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`", which, left, left_str, right, right_str),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`", which, msg, left, left_str, right, right_str),
+    }
 }
 
 #[cold]
 #[inline(never)]
+#[track_caller]
 #[doc(hidden)]
 #[cfg(not(feature = "inline_panics"))]
-pub fn cmp_assert_fail_msg<A, B>(which: &str, left: &A, right: &B, left_str: &str, right_str: &str, msg: core::fmt::Arguments<'_>) -> !
-where
-    A: core::fmt::Debug,
-    B: core::fmt::Debug,
-{
-    panic!("assertion failed: `chek::{}!(left, right): {}`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`", which, msg, left, left_str, right, right_str);
+pub fn eq_assert_fail(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&eq_failure(which, left, right, left_str, right_str, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, left, left_str, right, right_str, EqDiff(left, right)),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, left, left_str, right, right_str, EqDiff(left, right)),
+    }
 }
 
-// Re-export so that we don't have to know which of `core`/`std` is available in the macro.
+#[cold]
+#[inline(never)]
+#[track_caller]
 #[doc(hidden)]
-pub use core::hint::unreachable_unchecked;
+#[cfg(not(feature = "inline_panics"))]
+pub fn ord_assert_fail(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    ord: Option<Ordering>,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&ord_failure(which, left, right, left_str, right_str, ord, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, left, left_str, right, right_str, OrdOutcome(ord)),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, left, left_str, right, right_str, OrdOutcome(ord)),
+    }
+}
 
 #[cold]
 #[inline(never)]
+#[track_caller]
 #[doc(hidden)]
 #[cfg(not(feature = "inline_panics"))]
-pub fn value_assert_fail_msg<A>(which: &str, value: &A, value_str: &str, msg: core::fmt::Arguments<'_>) -> !
-where
-    A: core::fmt::Debug,
-{
-    panic!("assertion failed: `chek::{}!(value): {}`\n value: `{:?}` = `{}`", which, msg, value, value_str);
+pub fn range_assert_fail(
+    which: &'static str,
+    value: &dyn Debug,
+    range: &dyn Debug,
+    value_str: &'static str,
+    range_str: &'static str,
+    outcome: RangeOutcome,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&range_failure(which, value, range, value_str, range_str, outcome, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, value, value_str, range, range_str, outcome),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, value, value_str, range, range_str, outcome),
+    }
 }
 
 #[cold]
 #[inline(never)]
+#[track_caller]
 #[doc(hidden)]
 #[cfg(not(feature = "inline_panics"))]
-pub fn value_assert_fail<A>(which: &str, value: &A, value_str: &str) -> !
-where
-    A: core::fmt::Debug,
-{
-    panic!("assertion failed: `chek::{}!(value)`\n value: `{:?}` = `{}`", which, value, value_str);
+pub fn contains_assert_fail(
+    which: &'static str,
+    haystack: &dyn Debug,
+    needle: &dyn Debug,
+    haystack_str: &'static str,
+    needle_str: &'static str,
+    outcome: ContainsOutcome<'_>,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&contains_failure(which, haystack, needle, haystack_str, needle_str, outcome, msg));
+    }
+    let haystack = Preview(haystack);
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, haystack, haystack_str, needle, needle_str, outcome),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, haystack, haystack_str, needle, needle_str, outcome),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn slice_assert_fail(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    mismatch: SliceMismatch,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&slice_failure(which, left, right, left_str, right_str, mismatch, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, left, left_str, right, right_str, mismatch),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, left, left_str, right, right_str, mismatch),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn bytes_assert_fail(
+    which: &'static str,
+    left: &[u8],
+    right: &[u8],
+    left_str: &'static str,
+    right_str: &'static str,
+    mismatch: BytesMismatch,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&bytes_failure(which, left, right, left_str, right_str, mismatch, msg));
+    }
+    let (left_len, right_len, diff) = (ByteCount(left.len()), ByteCount(right.len()), HexDiff(left, right, mismatch));
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, left_len, left_str, right_len, right_str, diff),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, left_len, left_str, right_len, right_str, diff),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn details_assert_fail(
+    which: &'static str,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    left_str: &'static str,
+    right_str: &'static str,
+    details: &dyn Display,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&details_failure(which, left, right, left_str, right_str, details, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, left, left_str, right, right_str, details),
+        Some(msg) => panic!("assertion failed: `chek::{}!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}", which, msg, left, left_str, right, right_str, details),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn value_assert_fail(which: &'static str, value: &dyn Debug, value_str: &'static str, msg: Option<Arguments<'_>>) {
+    if let Some(handler) = failure_handler() {
+        return handler(&value_failure(which, value, value_str, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(value)`\n value: `{:?}` = `{}`", which, value, value_str),
+        Some(msg) => panic!("assertion failed: `chek::{}!(value)`: {}\n value: `{:?}` = `{}`", which, msg, value, value_str),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn value_details_assert_fail(
+    which: &'static str,
+    value: &dyn Debug,
+    value_str: &'static str,
+    details: &dyn Display,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&value_details_failure(which, value, value_str, details, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!(value)`\n value: `{:?}` = `{}`{}", which, value, value_str, details),
+        Some(msg) => panic!("assertion failed: `chek::{}!(value)`: {}\n value: `{:?}` = `{}`{}", which, msg, value, value_str, details),
+    }
+}
+
+/// Called when one of the unwrapping assertions (e.g. `chek::some!`) fails but
+/// can't report it and carry on, either because the failure handler returned,
+/// or because it's a `debug_` version with debug assertions off. Either way,
+/// there's no value to return.
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+pub fn no_value(which: &'static str) -> ! {
+    panic!("`chek::{}!` failed, and has no value to return", which)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn check_fail(which: &'static str, expr_str: &'static str, values: &[(&str, CheckValue<'_>)], msg: Option<Arguments<'_>>) {
+    if let Some(handler) = failure_handler() {
+        return handler(&check_failure(which, expr_str, values, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!({})`{}", which, expr_str, CheckValues(values)),
+        Some(msg) => panic!("assertion failed: `chek::{}!({})`: {}{}", which, expr_str, msg, CheckValues(values)),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn chain_fail(
+    which: &'static str,
+    expr_str: &'static str,
+    values: &[(&str, &dyn Debug)],
+    link_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&chain_failure(which, expr_str, values, link_str, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!({})`{}", which, expr_str, ChainValues(values, link_str)),
+        Some(msg) => panic!("assertion failed: `chek::{}!({})`: {}{}", which, expr_str, msg, ChainValues(values, link_str)),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn sorted_fail(
+    which: &'static str,
+    expr_str: &'static str,
+    index: usize,
+    left: &dyn Debug,
+    right: &dyn Debug,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&sorted_failure(which, expr_str, index, left, right, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!({})`{}", which, expr_str, UnsortedPair(index, left, right)),
+        Some(msg) => panic!("assertion failed: `chek::{}!({})`: {}{}", which, expr_str, msg, UnsortedPair(index, left, right)),
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[doc(hidden)]
+#[cfg(not(feature = "inline_panics"))]
+pub fn match_assert_fail(
+    which: &'static str,
+    value: &dyn Debug,
+    value_str: &'static str,
+    pat_str: &'static str,
+    msg: Option<Arguments<'_>>,
+) {
+    if let Some(handler) = failure_handler() {
+        return handler(&match_failure(which, value, value_str, pat_str, msg));
+    }
+    match msg {
+        None => panic!("assertion failed: `chek::{}!({}, {})`\n value: `{:?}`", which, value_str, pat_str, value),
+        Some(msg) => panic!("assertion failed: `chek::{}!({}, {})`: {}\n value: `{:?}`", which, value_str, pat_str, msg, value),
+    }
 }
 
 #[doc(hidden)]
@@ -53,10 +539,10 @@ where
 #[cfg(not(feature = "inline_panics"))]
 macro_rules! __cmp_assert_fail {
     ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
-        $crate::__internal::cmp_assert_fail($which, $left, $right, $left_str, $right_str)
+        $crate::__internal::cmp_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, None)
     };
     ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
-        $crate::__internal::cmp_assert_fail_msg($which, $left, $right, $left_str, $right_str, $msg)
+        $crate::__internal::cmp_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, Some($msg))
     };
 }
 
@@ -65,12 +551,248 @@ macro_rules! __cmp_assert_fail {
 #[cfg(feature = "inline_panics")]
 macro_rules! __cmp_assert_fail {
     ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
-        panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`"),
-               $left, stringify!($left), $right, stringify!($right))
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::cmp_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`"),
+                           $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::cmp_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`"),
+                           $msg, $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __eq_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
+        $crate::__internal::eq_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, None)
     };
     ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
-        panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`"),
-               $msg, $left, $left_str, $right, $right_str)
+        $crate::__internal::eq_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __eq_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::eq_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str, $crate::__internal::EqDiff($crate::__debug!($left), $crate::__debug!($right))),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::eq_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str, $crate::__internal::EqDiff($crate::__debug!($left), $crate::__debug!($right))),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __ord_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
+        $crate::__internal::ord_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, $crate::__cmp!(ordering, $left, $right), None)
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
+        $crate::__internal::ord_assert_fail($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str, $crate::__cmp!(ordering, $left, $right), Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __ord_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::ord_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str,
+                                                                      $crate::__cmp!(ordering, $left, $right), None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str, $crate::__internal::OrdOutcome($crate::__cmp!(ordering, $left, $right))),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::ord_failure($which, $crate::__debug!($left), $crate::__debug!($right), $left_str, $right_str,
+                                                                      $crate::__cmp!(ordering, $left, $right), Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $crate::__debug!($left), $left_str, $crate::__debug!($right), $right_str, $crate::__internal::OrdOutcome($crate::__cmp!(ordering, $left, $right))),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __range_assert_fail {
+    ($which:expr, $value:expr, $range:expr, $value_str:expr, $range_str:expr, $outcome:expr) => {
+        $crate::__internal::range_assert_fail($which, $value, $range, $value_str, $range_str, $outcome, None)
+    };
+    ($which:expr, $value:expr, $range:expr, $value_str:expr, $range_str:expr, $outcome:expr, $msg:expr) => {
+        $crate::__internal::range_assert_fail($which, $value, $range, $value_str, $range_str, $outcome, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __range_assert_fail {
+    ($which:expr, $value:expr, $range:expr, $value_str:expr, $range_str:expr, $outcome:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::range_failure($which, $value, $range, $value_str, $range_str, $outcome, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $value, $value_str, $range, $range_str, $outcome),
+        }
+    };
+    ($which:expr, $value:expr, $range:expr, $value_str:expr, $range_str:expr, $outcome:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::range_failure($which, $value, $range, $value_str, $range_str, $outcome, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $value, $value_str, $range, $range_str, $outcome),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __contains_assert_fail {
+    ($which:expr, $haystack:expr, $needle:expr, $haystack_str:expr, $needle_str:expr, $outcome:expr) => {
+        $crate::__internal::contains_assert_fail($which, $crate::__debug!($haystack), $crate::__debug!($needle), $haystack_str, $needle_str, $outcome, None)
+    };
+    ($which:expr, $haystack:expr, $needle:expr, $haystack_str:expr, $needle_str:expr, $outcome:expr, $msg:expr) => {
+        $crate::__internal::contains_assert_fail($which, $crate::__debug!($haystack), $crate::__debug!($needle), $haystack_str, $needle_str, $outcome, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __contains_assert_fail {
+    ($which:expr, $haystack:expr, $needle:expr, $haystack_str:expr, $needle_str:expr, $outcome:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::contains_failure($which, $crate::__debug!($haystack), $crate::__debug!($needle),
+                                                                           $haystack_str, $needle_str, $outcome, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $crate::__internal::Preview($crate::__debug!($haystack)), $haystack_str, $crate::__debug!($needle), $needle_str, $outcome),
+        }
+    };
+    ($which:expr, $haystack:expr, $needle:expr, $haystack_str:expr, $needle_str:expr, $outcome:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::contains_failure($which, $crate::__debug!($haystack), $crate::__debug!($needle),
+                                                                           $haystack_str, $needle_str, $outcome, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $crate::__internal::Preview($crate::__debug!($haystack)), $haystack_str, $crate::__debug!($needle), $needle_str, $outcome),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __slice_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr) => {
+        $crate::__internal::slice_assert_fail($which, $left, $right, $left_str, $right_str, $mismatch, None)
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr, $msg:expr) => {
+        $crate::__internal::slice_assert_fail($which, $left, $right, $left_str, $right_str, $mismatch, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __slice_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::slice_failure($which, $left, $right, $left_str, $right_str, $mismatch, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $left, $left_str, $right, $right_str, $mismatch),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::slice_failure($which, $left, $right, $left_str, $right_str, $mismatch, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $left, $left_str, $right, $right_str, $mismatch),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __bytes_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr) => {
+        $crate::__internal::bytes_assert_fail($which, $left, $right, $left_str, $right_str, $mismatch, None)
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr, $msg:expr) => {
+        $crate::__internal::bytes_assert_fail($which, $left, $right, $left_str, $right_str, $mismatch, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __bytes_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::bytes_failure($which, $left, $right, $left_str, $right_str, $mismatch, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $crate::__internal::ByteCount($left.len()), $left_str, $crate::__internal::ByteCount($right.len()), $right_str,
+                           $crate::__internal::HexDiff($left, $right, $mismatch)),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $mismatch:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::bytes_failure($which, $left, $right, $left_str, $right_str, $mismatch, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $crate::__internal::ByteCount($left.len()), $left_str, $crate::__internal::ByteCount($right.len()), $right_str,
+                           $crate::__internal::HexDiff($left, $right, $mismatch)),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __details_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $details:expr) => {
+        $crate::__internal::details_assert_fail($which, $left, $right, $left_str, $right_str, $details, None)
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $details:expr, $msg:expr) => {
+        $crate::__internal::details_assert_fail($which, $left, $right, $left_str, $right_str, $details, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __details_assert_fail {
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $details:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::details_failure($which, $left, $right, $left_str, $right_str, $details, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $left, $left_str, $right, $right_str, $details),
+        }
+    };
+    ($which:expr, $left:expr, $right:expr, $left_str:expr, $right_str:expr, $details:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::details_failure($which, $left, $right, $left_str, $right_str, $details, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(left, right)`: {}\n  left: `{:?}` = `{}`,\n right: `{:?}` = `{}`{}"),
+                           $msg, $left, $left_str, $right, $right_str, $details),
+        }
     };
 }
 
@@ -79,10 +801,10 @@ macro_rules! __cmp_assert_fail {
 #[cfg(not(feature = "inline_panics"))]
 macro_rules! __value_assert_fail {
     ($which:expr, $value:expr, $value_str:expr) => {
-        $crate::__internal::value_assert_fail($which, $value, $value_str)
+        $crate::__internal::value_assert_fail($which, $crate::__debug!($value), $value_str, None)
     };
     ($which:expr, $value:expr, $value_str:expr, $msg:expr) => {
-        $crate::__internal::value_assert_fail_msg($which, $value, $value_str, $msg)
+        $crate::__internal::value_assert_fail($which, $crate::__debug!($value), $value_str, Some($msg))
     };
 }
 
@@ -91,10 +813,835 @@ macro_rules! __value_assert_fail {
 #[cfg(feature = "inline_panics")]
 macro_rules! __value_assert_fail {
     ($which:expr, $value:expr, $value_str:expr) => {
-        panic!(concat!("assertion failed: `chek::", $which, "!(value)`\n value: `{:?}` = `{}`"), $value, $value_str);
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::value_failure($which, $crate::__debug!($value), $value_str, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(value)`\n value: `{:?}` = `{}`"), $crate::__debug!($value), $value_str),
+        }
     };
     ($which:expr, $value:expr, $value_str:expr, $msg:expr) => {
-        panic!(concat!("assertion failed: `chek::", $which, "!(value)`: {}\n value: `{:?}` = `{}`"), $msg, $value, $value_str);
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::value_failure($which, $crate::__debug!($value), $value_str, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(value)`: {}\n value: `{:?}` = `{}`"), $msg, $crate::__debug!($value), $value_str),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __value_details_assert_fail {
+    ($which:expr, $value:expr, $value_str:expr, $details:expr) => {
+        $crate::__internal::value_details_assert_fail($which, $value, $value_str, $details, None)
+    };
+    ($which:expr, $value:expr, $value_str:expr, $details:expr, $msg:expr) => {
+        $crate::__internal::value_details_assert_fail($which, $value, $value_str, $details, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __value_details_assert_fail {
+    ($which:expr, $value:expr, $value_str:expr, $details:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::value_details_failure($which, $value, $value_str, $details, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(value)`\n value: `{:?}` = `{}`{}"), $value, $value_str, $details),
+        }
+    };
+    ($which:expr, $value:expr, $value_str:expr, $details:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::value_details_failure($which, $value, $value_str, $details, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!(value)`: {}\n value: `{:?}` = `{}`{}"),
+                           $msg, $value, $value_str, $details),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __check_fail {
+    ($which:expr, $expr_str:expr, $values:expr) => {
+        $crate::__internal::check_fail($which, $expr_str, $values, None)
+    };
+    ($which:expr, $expr_str:expr, $values:expr, $msg:expr) => {
+        $crate::__internal::check_fail($which, $expr_str, $values, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __check_fail {
+    ($which:expr, $expr_str:expr, $values:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::check_failure($which, $expr_str, $values, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`{}"),
+                           $expr_str, $crate::__internal::CheckValues($values)),
+        }
+    };
+    ($which:expr, $expr_str:expr, $values:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::check_failure($which, $expr_str, $values, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`: {}{}"),
+                           $expr_str, $msg, $crate::__internal::CheckValues($values)),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __chain_fail {
+    ($which:expr, $expr_str:expr, $values:expr, $link_str:expr) => {
+        $crate::__internal::chain_fail($which, $expr_str, $values, $link_str, None)
+    };
+    ($which:expr, $expr_str:expr, $values:expr, $link_str:expr, $msg:expr) => {
+        $crate::__internal::chain_fail($which, $expr_str, $values, $link_str, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __chain_fail {
+    ($which:expr, $expr_str:expr, $values:expr, $link_str:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::chain_failure($which, $expr_str, $values, $link_str, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`{}"),
+                           $expr_str, $crate::__internal::ChainValues($values, $link_str)),
+        }
+    };
+    ($which:expr, $expr_str:expr, $values:expr, $link_str:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::chain_failure($which, $expr_str, $values, $link_str, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`: {}{}"),
+                           $expr_str, $msg, $crate::__internal::ChainValues($values, $link_str)),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __sorted_fail {
+    ($which:expr, $expr_str:expr, $index:expr, $left:expr, $right:expr) => {
+        $crate::__internal::sorted_fail($which, $expr_str, $index, $left, $right, None)
+    };
+    ($which:expr, $expr_str:expr, $index:expr, $left:expr, $right:expr, $msg:expr) => {
+        $crate::__internal::sorted_fail($which, $expr_str, $index, $left, $right, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __sorted_fail {
+    ($which:expr, $expr_str:expr, $index:expr, $left:expr, $right:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::sorted_failure($which, $expr_str, $index, $left, $right, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`{}"),
+                           $expr_str, $crate::__internal::UnsortedPair($index, $left, $right)),
+        }
+    };
+    ($which:expr, $expr_str:expr, $index:expr, $left:expr, $right:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::sorted_failure($which, $expr_str, $index, $left, $right, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({})`: {}{}"),
+                           $expr_str, $msg, $crate::__internal::UnsortedPair($index, $left, $right)),
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "inline_panics"))]
+macro_rules! __match_assert_fail {
+    ($which:expr, $value:expr, $value_str:expr, $pat_str:expr) => {
+        $crate::__internal::match_assert_fail($which, $crate::__debug!($value), $value_str, $pat_str, None)
+    };
+    ($which:expr, $value:expr, $value_str:expr, $pat_str:expr, $msg:expr) => {
+        $crate::__internal::match_assert_fail($which, $crate::__debug!($value), $value_str, $pat_str, Some($msg))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "inline_panics")]
+macro_rules! __match_assert_fail {
+    ($which:expr, $value:expr, $value_str:expr, $pat_str:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::match_failure($which, $crate::__debug!($value), $value_str, $pat_str, None)),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({}, {})`\n value: `{:?}`"), $value_str, $pat_str, $crate::__debug!($value)),
+        }
+    };
+    ($which:expr, $value:expr, $value_str:expr, $pat_str:expr, $msg:expr) => {
+        match $crate::__internal::failure_handler() {
+            Some(handler) => handler(&$crate::__internal::match_failure($which, $crate::__debug!($value), $value_str, $pat_str, Some($msg))),
+            None => panic!(concat!("assertion failed: `chek::", $which, "!({}, {})`: {}\n value: `{:?}`"), $value_str, $pat_str, $msg, $crate::__debug!($value)),
+        }
+    };
+}
+
+#[doc(hidden)]
+pub use crate::approx::{first_mismatch, MismatchDetails};
+
+#[doc(hidden)]
+pub use crate::cmp_pair::{CmpPair, DerefBothEqPair, DerefLeftEqPair, DerefRightEqPair, IntCmpPair, PartialEqPair, PartialOrdPair};
+
+/// `chek::__cmp!(is_lt, left, right)` compares two references with `is_lt`
+/// (or `is_le`, `is_gt`, `is_ge`, `is_eq`, or `ordering` for an
+/// `Option<Ordering>`), using the first of the traits listed in `cmp_pair.rs`
+/// that applies.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cmp {
+    ($op:ident, $left:expr, $right:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{
+            DerefBothEqPair as _, DerefLeftEqPair as _, DerefRightEqPair as _, IntCmpPair as _, PartialEqPair as _, PartialOrdPair as _,
+        };
+        (&&&&&$crate::__internal::CmpPair($left, $right)).$op()
+    }};
+}
+
+#[doc(hidden)]
+pub use crate::maybe_debug::{DebugValue, MaybeDebug, NoDebugValue};
+
+/// `chek::__debug!(value)` turns a reference into a `&dyn Debug`, which prints
+/// a placeholder with the type's name if it doesn't implement `Debug`. See
+/// `maybe_debug.rs`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __debug {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{DebugValue as _, NoDebugValue as _};
+        (&&$crate::__internal::MaybeDebug(&$value)).as_debug()
+    }};
+}
+
+#[doc(hidden)]
+pub use crate::collection::{
+    as_map, ContainsDerefNeedle, ContainsNeedle, ContainsOutcome, DerefContainsDerefNeedle, DerefContainsNeedle, NeedlePair, Preview,
+};
+
+/// `chek::__contains!(haystack, needle)` looks for a needle with
+/// `chek::Contains`, returning a `ContainsOutcome`. If the haystack doesn't
+/// implement `Contains` for the needle, this tries again after dereferencing
+/// either or both of them. See `collection.rs`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __contains {
+    ($haystack:expr, $needle:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{
+            ContainsDerefNeedle as _, ContainsNeedle as _, DerefContainsDerefNeedle as _, DerefContainsNeedle as _,
+        };
+        (&&&&$crate::__internal::NeedlePair($haystack, $needle)).outcome()
+    }};
+}
+
+#[doc(hidden)]
+pub use crate::multiset::{elements_pair, ElementsDiff, ElementsPair, HashElements, OrdElements, ScanElements};
+
+/// `chek::__same_elements!(left, right)` compares two slices as multisets,
+/// returning `Some(ElementsDiff)` if they differ. This counts the elements by
+/// hashing or sorting them if it can, and by scanning otherwise. See
+/// `multiset.rs`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __same_elements {
+    ($left:expr, $right:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{HashElements as _, OrdElements as _, ScanElements as _};
+        (&&&$crate::__internal::elements_pair($left, $right)).elements_diff()
+    }};
+}
+
+#[doc(hidden)]
+pub use crate::float::{
+    abs_diff_le, bits_eq, class_mismatch, float_diff, rel_diff_le, total_cmp, ulps_le, BitsDiff, ClassMismatch, ClassValue, Float,
+    FloatClass, FloatDiff, Floats, TotalOrder,
+};
+
+#[doc(hidden)]
+pub use crate::hex::{bytes_mismatch, ByteCount, BytesMismatch, HexDiff};
+
+/// Used by `chek::bytes_eq!` to accept anything that can be viewed as bytes.
+#[doc(hidden)]
+#[inline(always)]
+pub fn as_bytes<T: AsRef<[u8]> + ?Sized>(value: &T) -> &[u8] {
+    value.as_ref()
+}
+
+// Re-export so that we don't have to know which of `core`/`std` is available in the macro.
+#[doc(hidden)]
+pub use core::hint::unreachable_unchecked;
+
+/// A diff of the two sides of a failed `chek::equal!`, which is shown if the
+/// `diff` feature is enabled and either side is more than one line long when
+/// pretty-printed. Formats as nothing otherwise.
+#[doc(hidden)]
+pub struct EqDiff<'a>(pub &'a dyn Debug, pub &'a dyn Debug);
+
+impl core::fmt::Display for EqDiff<'_> {
+    #[cfg(feature = "diff")]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::diff::write_diff(f, self.0, self.1)
+    }
+
+    #[cfg(not(feature = "diff"))]
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Ok(())
+    }
+}
+
+/// Formats the result of `partial_cmp` for a failed ordering assertion.
+#[doc(hidden)]
+pub struct OrdOutcome(pub Option<Ordering>);
+
+impl core::fmt::Display for OrdOutcome {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self.0 {
+            Some(Ordering::Less) => "\nresult: left was less than right",
+            Some(Ordering::Equal) => "\nresult: left was equal to right",
+            Some(Ordering::Greater) => "\nresult: left was greater than right",
+            None => "\nresult: values are incomparable (partial_cmp returned None)",
+        })
+    }
+}
+
+/// Where a value was found relative to a range, as determined by `range_outcome`.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RangeOutcome {
+    Within,
+    BelowStart,
+    AtExcludedStart,
+    IncomparableToStart,
+    AboveEnd,
+    AtExcludedEnd,
+    IncomparableToEnd,
+}
+
+impl RangeOutcome {
+    #[inline]
+    pub fn is_within(self) -> bool {
+        self == RangeOutcome::Within
+    }
+}
+
+/// Used by `chek::in_range!` and `chek::not_in_range!` to check the value
+/// against each bound of the range, so that we can say which one it violated.
+#[doc(hidden)]
+#[inline]
+pub fn range_outcome<T: PartialOrd + ?Sized, R: RangeBounds<T> + ?Sized>(value: &T, range: &R) -> RangeOutcome {
+    let start = match range.start_bound() {
+        Bound::Included(start) => match value.partial_cmp(start) {
+            Some(Ordering::Less) => RangeOutcome::BelowStart,
+            None => RangeOutcome::IncomparableToStart,
+            _ => RangeOutcome::Within,
+        },
+        Bound::Excluded(start) => match value.partial_cmp(start) {
+            Some(Ordering::Less) => RangeOutcome::BelowStart,
+            Some(Ordering::Equal) => RangeOutcome::AtExcludedStart,
+            None => RangeOutcome::IncomparableToStart,
+            _ => RangeOutcome::Within,
+        },
+        Bound::Unbounded => RangeOutcome::Within,
     };
+    if !start.is_within() {
+        return start;
+    }
+    match range.end_bound() {
+        Bound::Included(end) => match value.partial_cmp(end) {
+            Some(Ordering::Greater) => RangeOutcome::AboveEnd,
+            None => RangeOutcome::IncomparableToEnd,
+            _ => RangeOutcome::Within,
+        },
+        Bound::Excluded(end) => match value.partial_cmp(end) {
+            Some(Ordering::Greater) => RangeOutcome::AboveEnd,
+            Some(Ordering::Equal) => RangeOutcome::AtExcludedEnd,
+            None => RangeOutcome::IncomparableToEnd,
+            _ => RangeOutcome::Within,
+        },
+        Bound::Unbounded => RangeOutcome::Within,
+    }
+}
+
+impl core::fmt::Display for RangeOutcome {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            RangeOutcome::Within => "\nresult: left was within right",
+            RangeOutcome::BelowStart => "\nresult: left was less than the start of right",
+            RangeOutcome::AtExcludedStart => "\nresult: left was equal to the start of right, which is excluded",
+            RangeOutcome::IncomparableToStart => {
+                "\nresult: left is incomparable to the start of right (partial_cmp returned None)"
+            }
+            RangeOutcome::AboveEnd => "\nresult: left was greater than the end of right",
+            RangeOutcome::AtExcludedEnd => "\nresult: left was equal to the end of right, which is excluded",
+            RangeOutcome::IncomparableToEnd => "\nresult: left is incomparable to the end of right (partial_cmp returned None)",
+        })
+    }
+}
+
+/// Used by the sortedness assertions to find the first pair of neighbors for
+/// which `in_order` returns false. Returns the index of the first item of the
+/// pair, and both items.
+#[doc(hidden)]
+#[inline]
+pub fn unsorted_pair<I: IntoIterator>(
+    iter: I,
+    mut in_order: impl FnMut(&I::Item, &I::Item) -> bool,
+) -> Option<(usize, I::Item, I::Item)> {
+    let mut iter = iter.into_iter();
+    let mut prev = iter.next()?;
+    for (index, item) in iter.enumerate() {
+        if !in_order(&prev, &item) {
+            return Some((index, prev, item));
+        }
+        prev = item;
+    }
+    None
+}
+
+/// The items given to `chek::sorted_by!` or `chek::sorted_by_key!`. These
+/// take a comparison or key function like `slice::sort_by` and
+/// `slice::sort_by_key` do, so for items of type `&T` (e.g. from a slice), the
+/// function should get `&T` rather than `&&T`. `chek::__unsorted_pair!` does
+/// that via `RefItems` if it can, and falls back to `ValueItems` otherwise.
+/// This is the same "autoref specialization" trick as in `cmp_pair.rs`, and
+/// the items are in a `Cell` so that they can be moved out from behind the
+/// `&self` it needs.
+#[doc(hidden)]
+pub struct SortedItems<I>(core::cell::Cell<Option<I>>);
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn sorted_items<I: IntoIterator>(items: I) -> SortedItems<I> {
+    SortedItems(core::cell::Cell::new(Some(items)))
+}
+
+impl<I> SortedItems<I> {
+    fn take(&self) -> I {
+        self.0.take().expect("items already taken")
+    }
 }
 
+#[doc(hidden)]
+pub trait RefItems {
+    type Item;
+    type Target: ?Sized;
+    fn unsorted_pair_by(&self, compare: impl FnMut(&Self::Target, &Self::Target) -> Ordering) -> Option<(usize, Self::Item, Self::Item)>;
+    fn unsorted_pair_by_key<K: PartialOrd>(&self, key: impl FnMut(&Self::Target) -> K) -> Option<(usize, Self::Item, Self::Item)>;
+}
+
+impl<'a, T: ?Sized + 'a, I: IntoIterator<Item = &'a T>> RefItems for &SortedItems<I> {
+    type Item = &'a T;
+    type Target = T;
+
+    #[inline]
+    fn unsorted_pair_by(&self, mut compare: impl FnMut(&T, &T) -> Ordering) -> Option<(usize, &'a T, &'a T)> {
+        unsorted_pair(self.take(), |a, b| compare(a, b) != Ordering::Greater)
+    }
+
+    #[inline]
+    fn unsorted_pair_by_key<K: PartialOrd>(&self, mut key: impl FnMut(&T) -> K) -> Option<(usize, &'a T, &'a T)> {
+        unsorted_pair(self.take(), |a, b| key(a) <= key(b))
+    }
+}
+
+#[doc(hidden)]
+pub trait ValueItems {
+    type Item;
+    fn unsorted_pair_by(&self, compare: impl FnMut(&Self::Item, &Self::Item) -> Ordering) -> Option<(usize, Self::Item, Self::Item)>;
+    fn unsorted_pair_by_key<K: PartialOrd>(&self, key: impl FnMut(&Self::Item) -> K) -> Option<(usize, Self::Item, Self::Item)>;
+}
+
+impl<I: IntoIterator> ValueItems for SortedItems<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn unsorted_pair_by(&self, mut compare: impl FnMut(&I::Item, &I::Item) -> Ordering) -> Option<(usize, I::Item, I::Item)> {
+        unsorted_pair(self.take(), |a, b| compare(a, b) != Ordering::Greater)
+    }
+
+    #[inline]
+    fn unsorted_pair_by_key<K: PartialOrd>(&self, mut key: impl FnMut(&I::Item) -> K) -> Option<(usize, I::Item, I::Item)> {
+        unsorted_pair(self.take(), |a, b| key(a) <= key(b))
+    }
+}
+
+/// `chek::__unsorted_pair!(by, items, compare)` and
+/// `chek::__unsorted_pair!(by_key, items, key)` are `unsorted_pair` for
+/// `chek::sorted_by!` and `chek::sorted_by_key!`. See `SortedItems`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unsorted_pair {
+    (by, $items:expr, $compare:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{RefItems as _, ValueItems as _};
+        (&&$crate::__internal::sorted_items($items)).unsorted_pair_by($compare)
+    }};
+    (by_key, $items:expr, $key:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{RefItems as _, ValueItems as _};
+        (&&$crate::__internal::sorted_items($items)).unsorted_pair_by_key($key)
+    }};
+}
+
+/// Where two slices compared by `chek::slice_eq!` first differ.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct SliceMismatch {
+    index: usize,
+    left_len: usize,
+    right_len: usize,
+}
+
+/// Returns where `left` and `right` first differ, or `None` if they're equal.
+/// This takes `AsRef` rather than just slices so that `&&[T]`, `&Vec<T>` and
+/// `&[T; N]` all work.
+#[doc(hidden)]
+#[inline]
+pub fn slice_mismatch<L, R, A, B>(left: &L, right: &R) -> Option<SliceMismatch>
+where
+    L: AsRef<[A]> + ?Sized,
+    R: AsRef<[B]> + ?Sized,
+    A: PartialEq<B>,
+{
+    let (left, right) = (left.as_ref(), right.as_ref());
+    let (left_len, right_len) = (left.len(), right.len());
+    match left.iter().zip(right).position(|(a, b)| a != b) {
+        Some(index) => Some(SliceMismatch { index, left_len, right_len }),
+        None if left_len != right_len => Some(SliceMismatch { index: left_len.min(right_len), left_len, right_len }),
+        None => None,
+    }
+}
+
+impl SliceMismatch {
+    /// An excerpt position for the element at `index` of a single slice.
+    #[inline]
+    pub(crate) fn element(index: usize, len: usize) -> Self {
+        SliceMismatch { index, left_len: len, right_len: len }
+    }
+}
+
+impl core::fmt::Display for SliceMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let SliceMismatch { index, left_len, right_len } = *self;
+        if left_len == right_len {
+            write!(f, "\nresult: first difference at index {} (both have length {})", index, left_len)
+        } else if index < left_len.min(right_len) {
+            write!(f, "\nresult: first difference at index {} (lengths are {} and {})", index, left_len, right_len)
+        } else {
+            write!(f, "\nresult: lengths are {} and {}, but the first {} elements are equal", left_len, right_len, index)
+        }
+    }
+}
+
+/// The elements of a slice around a `SliceMismatch`, which is what
+/// `chek::slice_eq!` prints instead of the whole slice.
+#[doc(hidden)]
+pub struct SliceExcerpt<'a, T>(&'a [T], SliceMismatch);
+
+/// How many elements on either side of the first difference are printed.
+const EXCERPT_CONTEXT: usize = 3;
+
+#[doc(hidden)]
+#[inline]
+pub fn slice_excerpt<S: AsRef<[T]> + ?Sized, T: Debug>(slice: &S, mismatch: SliceMismatch) -> SliceExcerpt<'_, T> {
+    SliceExcerpt(slice.as_ref(), mismatch)
+}
+
+impl<T: Debug> Debug for SliceExcerpt<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let start = self.1.index.saturating_sub(EXCERPT_CONTEXT).min(self.0.len());
+        let end = self.1.index.saturating_add(EXCERPT_CONTEXT + 1).min(self.0.len());
+        f.write_str("[")?;
+        if start > 0 {
+            f.write_str("...")?;
+        }
+        for (i, item) in self.0[start..end].iter().enumerate() {
+            if start > 0 || i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{:?}", item)?;
+        }
+        if end < self.0.len() {
+            f.write_str(", ...")?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Where the elements of two float slices compared by
+/// `chek::all_almost_equal!` (or of one slice compared by
+/// `chek::all_almost_zero!`) first fail, and how many fail in total.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct ElementsMismatch<'a> {
+    at: SliceMismatch,
+    failed: usize,
+    // The first failing element(s). `None` if only the lengths differ, and no
+    // right element for `chek::all_almost_zero!`.
+    first: Option<(&'a dyn Debug, Option<&'a dyn Debug>)>,
+}
+
+impl ElementsMismatch<'_> {
+    /// Where to excerpt the slices around.
+    #[inline]
+    pub fn at(&self) -> SliceMismatch {
+        self.at
+    }
+}
+
+/// Used by `chek::all_almost_equal!`. Unlike `slice_mismatch`, this keeps going
+/// after the first difference, to count them.
+#[doc(hidden)]
+pub fn all_almost_equal_mismatch<'a, L, R, F>(left: &'a L, right: &'a R) -> Option<ElementsMismatch<'a>>
+where
+    L: AsRef<[F]> + ?Sized,
+    R: AsRef<[F]> + ?Sized,
+    F: almost::AlmostEqual + Copy + Debug + 'a,
+{
+    let (left, right) = (left.as_ref(), right.as_ref());
+    let (left_len, right_len) = (left.len(), right.len());
+    let mut first = None;
+    let mut failed = 0;
+    for (index, (a, b)) in left.iter().zip(right).enumerate() {
+        if !almost::equal(*a, *b) {
+            first.get_or_insert((index, (a as &dyn Debug, Some(b as &dyn Debug))));
+            failed += 1;
+        }
+    }
+    match first {
+        Some((index, first)) => Some(ElementsMismatch { at: SliceMismatch { index, left_len, right_len }, failed, first: Some(first) }),
+        None if left_len != right_len => {
+            let at = SliceMismatch { index: left_len.min(right_len), left_len, right_len };
+            Some(ElementsMismatch { at, failed, first: None })
+        }
+        None => None,
+    }
+}
+
+/// Used by `chek::all_almost_zero!`.
+#[doc(hidden)]
+pub fn all_almost_zero_mismatch<'a, S, F>(value: &'a S) -> Option<ElementsMismatch<'a>>
+where
+    S: AsRef<[F]> + ?Sized,
+    F: almost::AlmostEqual + Copy + Debug + 'a,
+{
+    let value = value.as_ref();
+    let mut first = None;
+    let mut failed = 0;
+    for (index, v) in value.iter().enumerate() {
+        if !almost::zero(*v) {
+            first.get_or_insert((index, v as &dyn Debug));
+            failed += 1;
+        }
+    }
+    let (index, first) = first?;
+    let at = SliceMismatch { index, left_len: value.len(), right_len: value.len() };
+    Some(ElementsMismatch { at, failed, first: Some((first, None)) })
+}
+
+impl core::fmt::Display for ElementsMismatch<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let SliceMismatch { index, left_len, right_len } = self.at;
+        f.write_str("\nresult: ")?;
+        match self.first {
+            Some((value, None)) => write!(
+                f,
+                "{} of {} elements are not almost zero, the first at index {}: `{:?}`",
+                self.failed, left_len, index, value
+            ),
+            Some((left, Some(right))) => {
+                if left_len == right_len {
+                    write!(f, "{} of {} elements differ", self.failed, left_len)?;
+                } else {
+                    let len = left_len.min(right_len);
+                    write!(f, "lengths are {} and {}, and {} of the first {} elements differ", left_len, right_len, self.failed, len)?;
+                }
+                write!(f, ", the first at index {}: `{:?}` vs `{:?}`", index, left, right)
+            }
+            None => write!(f, "lengths are {} and {}, but the first {} elements are almost equal", left_len, right_len, index),
+        }
+    }
+}
+
+/// Used by `chek::check!` to stash the value of a subexpression. The slot is
+/// always empty when this is called, since each slot is only written once.
+#[doc(hidden)]
+#[inline(always)]
+pub fn check_store<T>(slot: &mut Option<T>, value: T) -> &T {
+    slot.get_or_insert(value)
+}
+
+/// Used by `chek::check!` to get the values it stashed for reporting.
+#[doc(hidden)]
+#[inline(always)]
+pub fn check_slot<T: Debug>(slot: &Option<T>) -> CheckValue<'_> {
+    match slot {
+        Some(value) => CheckValue::Value(value),
+        None => CheckValue::NotEvaluated,
+    }
+}
+
+/// Used by `chek::check!` to get a value that `CopyValue::record` stashed.
+#[doc(hidden)]
+#[inline(always)]
+pub fn check_copied<T: Debug>(slot: &Option<Option<T>>) -> CheckValue<'_> {
+    match slot {
+        Some(Some(value)) => CheckValue::Value(value),
+        Some(None) => CheckValue::NotCopied,
+        None => CheckValue::NotEvaluated,
+    }
+}
+
+/// What `chek::check!` has to report for one subexpression.
+#[doc(hidden)]
+pub enum CheckValue<'a> {
+    Value(&'a dyn Debug),
+    /// Skipped due to short-circuiting.
+    NotEvaluated,
+    /// Evaluated, but not `Copy` (or `Debug`), so it wasn't recorded. These
+    /// aren't reported at all.
+    NotCopied,
+}
+
+/// Used by `chek::check!` to record a receiver, argument or arithmetic operand,
+/// which the expression still needs after it's been recorded. Calling `record`
+/// on `&&CheckCopy(&value)` stashes a copy if the value is `Copy` and `Debug`
+/// (via `CopyValue`), and just notes that it was evaluated otherwise (via
+/// `NoCopyValue`), since moving or borrowing it could change what the
+/// expression does. This is the same "autoref specialization" trick as in
+/// `cmp_pair.rs`.
+#[doc(hidden)]
+pub struct CheckCopy<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait CopyValue<T> {
+    fn record(&self, slot: &mut Option<Option<T>>);
+}
+
+impl<T: Copy + Debug> CopyValue<T> for &CheckCopy<'_, T> {
+    #[inline(always)]
+    fn record(&self, slot: &mut Option<Option<T>>) {
+        *slot = Some(Some(*self.0));
+    }
+}
+
+#[doc(hidden)]
+pub trait NoCopyValue {
+    fn record(&self, slot: &mut Option<Option<()>>);
+}
+
+impl<T: ?Sized> NoCopyValue for CheckCopy<'_, T> {
+    #[inline(always)]
+    fn record(&self, slot: &mut Option<Option<()>>) {
+        *slot = Some(None);
+    }
+}
+
+/// Formats the subexpressions recorded by `chek::check!`, one per line.
+#[doc(hidden)]
+pub struct CheckValues<'a>(pub &'a [(&'a str, CheckValue<'a>)]);
+
+impl core::fmt::Display for CheckValues<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let reported = || self.0.iter().filter(|(_, value)| !matches!(value, CheckValue::NotCopied));
+        let width = reported().map(|(s, _)| s.chars().count()).max().unwrap_or(0);
+        for (expr, value) in reported() {
+            match value {
+                CheckValue::Value(value) => write!(f, "\n  {:>w$}: `{:?}`", expr, value, w = width)?,
+                CheckValue::NotEvaluated => write!(f, "\n  {:>w$}: (not evaluated)", expr, w = width)?,
+                CheckValue::NotCopied => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Formats the operands recorded by `chek::ordered!`, one per line, followed by
+/// the link in the chain that didn't hold.
+#[doc(hidden)]
+pub struct ChainValues<'a>(pub &'a [(&'a str, &'a dyn Debug)], pub &'a str);
+
+impl core::fmt::Display for ChainValues<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        const FAILED_AT: &str = "failed at";
+        let width = self.0.iter().map(|(s, _)| s.chars().count()).fold(FAILED_AT.len(), usize::max);
+        for (expr, value) in self.0 {
+            write!(f, "\n  {:>w$}: `{:?}`", expr, value, w = width)?;
+        }
+        write!(f, "\n  {:>w$}: `{}`", FAILED_AT, self.1, w = width)
+    }
+}
+
+/// Formats the out-of-order neighbors found by the sortedness assertions.
+#[doc(hidden)]
+pub struct UnsortedPair<'a>(pub usize, pub &'a dyn Debug, pub &'a dyn Debug);
+
+impl core::fmt::Display for UnsortedPair<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let UnsortedPair(index, left, right) = *self;
+        write!(f, "\n  left: `{:?}` (at index {}),\n right: `{:?}` (at index {})", left, index, right, index + 1)
+    }
+}
+
+/// The error given to an `ensure_` macro. `chek::__ensure_error!` calls it with
+/// the `chek::Failure` if it's a function or closure taking one, and returns it
+/// as is otherwise. This uses "autoref specialization": method resolution
+/// tries the impl for `&EnsureError<F>` before the one for `EnsureError<E>`.
+/// The error is in a `Cell` so that it can be moved out from behind the
+/// `&self` that trick needs.
+#[doc(hidden)]
+pub struct EnsureError<E>(core::cell::Cell<Option<E>>);
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn ensure_error<E>(error: E) -> EnsureError<E> {
+    EnsureError(core::cell::Cell::new(Some(error)))
+}
+
+#[doc(hidden)]
+pub trait CallEnsureError {
+    type Error;
+    fn take_error(&self, failure: crate::Failure) -> Self::Error;
+}
+
+impl<F: FnOnce(crate::Failure) -> E, E> CallEnsureError for &EnsureError<F> {
+    type Error = E;
+    #[inline]
+    fn take_error(&self, failure: crate::Failure) -> E {
+        self.0.take().expect("error already taken")(failure)
+    }
+}
+
+#[doc(hidden)]
+pub trait ValueEnsureError {
+    type Error;
+    fn take_error(&self, failure: crate::Failure) -> Self::Error;
+}
+
+impl<E> ValueEnsureError for EnsureError<E> {
+    type Error = E;
+    #[inline]
+    fn take_error(&self, _: crate::Failure) -> E {
+        self.0.take().expect("error already taken")
+    }
+}
+
+/// `chek::__ensure_error!(err, failure)` is the error an `ensure_` macro
+/// returns when given `err`: `err(failure)` if that works, and `err` otherwise.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ensure_error {
+    ($err:expr, $failure:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__internal::{CallEnsureError as _, ValueEnsureError as _};
+        (&&$crate::__internal::ensure_error($err)).take_error($failure)
+    }};
+}
@@ -0,0 +1,346 @@
+
+/// Panics unless the two slices have the same length, and each element of
+/// `left` is almost equal to the element of `right` at the same index.
+///
+/// Elements are compared the same way as by `chek::almost_equal!`, using
+/// [`almost::equal`]. Takes anything that implements `AsRef<[f32]>` or
+/// `AsRef<[f64]>`, e.g. arrays, slices and `Vec`s.
+///
+/// Rather than the whole slices, the failure message shows the elements around
+/// the first failing index, the two values there, and how many elements failed
+/// in total. This doesn't allocate, so it works without the `alloc` feature.
+///
+/// [`almost::equal`]: https://docs.rs/almost/%2a/almost/fn.equal.html
+///
+/// A debug-only version is available as `chek::debug_all_almost_equal!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let samples = [0.1 + 0.2, 0.5, 1.0 / 3.0];
+/// chek::all_almost_equal!(samples, [0.3, 0.5, 0.3333333333333333]);
+/// chek::all_almost_equal!(&samples[..2], vec![0.3, 0.5], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = ([1.0f32, 2.0, 3.0, 4.0], [1.0f32, 2.0, 3.5, 4.5]);
+/// // assertion failed: `chek::all_almost_equal!(left, right)`
+/// //   left: `[1.0, 2.0, 3.0, 4.0]` = `x`,
+/// //  right: `[1.0, 2.0, 3.5, 4.5]` = `y`
+/// // result: 2 of 4 elements differ, the first at index 2: `3.0` vs `3.5`
+/// chek::all_almost_equal!(x, y);
+/// ```
+#[macro_export]
+macro_rules! all_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::all_almost_equal_mismatch(left, right) {
+            let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+            $crate::__details_assert_fail!("all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch);
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::all_almost_equal_mismatch(left, right) {
+            let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+            $crate::__details_assert_fail!("all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch,
+                                         format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::all_almost_equal!` in debug builds or release builds where
+/// the `-C debug-assertions` was provided to the compiler. For all other
+/// builds, vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let samples = [0.1 + 0.2, 0.5, 1.0 / 3.0];
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_all_almost_equal!(samples, [0.3, 0.5, 0.3333333333333333]);
+/// chek::debug_all_almost_equal!(&samples[..2], vec![0.3, 0.5], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_all_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::all_almost_equal_mismatch(left, right) {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+                $crate::__details_assert_fail!("debug_all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch);
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::all_almost_equal_mismatch(left, right) {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+                $crate::__details_assert_fail!("debug_all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch,
+                                             format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` unless the two slices have the same length and
+/// are almost equal element by element, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::all_almost_equal!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_all_almost_equal!([0.1 + 0.2, 0.5], [0.3, 0.5]).is_ok());
+/// assert!(chek::try_all_almost_equal!([0.3, 0.5], [0.3], "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_all_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__internal::all_almost_equal_mismatch(left, right) {
+            Some(mismatch) => {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+                Err($crate::__internal::details_failure("all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch, None))
+            }
+            None => Ok(()),
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__internal::all_almost_equal_mismatch(left, right) {
+            Some(mismatch) => {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch.at()), $crate::__internal::slice_excerpt(right, mismatch.at()));
+                Err($crate::__internal::details_failure("all_almost_equal", &left, &right, stringify!($left), stringify!($right), &mismatch,
+                                                      Some(format_args!($($msg_args)+))))
+            }
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function unless the two
+/// slices have the same length and are almost equal element by element.
+///
+/// This is the `Result`-returning version of `chek::all_almost_equal!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_all_almost_equal!([0.1 + 0.2, 0.5], [0.3, 0.5]);
+///     chek::ensure_all_almost_equal!([0.3, 0.5], [0.3, 0.6]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_all_almost_equal!([0.3, 0.5], [0.3], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_all_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_all_almost_equal!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_all_almost_equal!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless every element of the slice is almost equal to zero.
+///
+/// Elements are checked the same way as by `chek::almost_zero!`, using
+/// [`almost::zero`]. Takes anything that implements `AsRef<[f32]>` or
+/// `AsRef<[f64]>`, e.g. arrays, slices and `Vec`s.
+///
+/// Rather than the whole slice, the failure message shows the elements around
+/// the first failing index, the value there, and how many elements failed in
+/// total. This doesn't allocate, so it works without the `alloc` feature.
+///
+/// [`almost::zero`]: https://docs.rs/almost/%2a/almost/fn.zero.html
+///
+/// A debug-only version is available as `chek::debug_all_almost_zero!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let residuals = [0.1 + 0.2 - 0.3, 0.0, -1e-20];
+/// chek::all_almost_zero!(residuals);
+/// chek::all_almost_zero!(&residuals[1..], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let residuals = [0.0f32, 0.5, 0.0, -0.25];
+/// // assertion failed: `chek::all_almost_zero!(value)`
+/// //  value: `[0.0, 0.5, 0.0, -0.25]` = `residuals`
+/// // result: 2 of 4 elements are not almost zero, the first at index 1: `0.5`
+/// chek::all_almost_zero!(residuals);
+/// ```
+#[macro_export]
+macro_rules! all_almost_zero {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::all_almost_zero_mismatch(value) {
+            let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+            $crate::__value_details_assert_fail!("all_almost_zero", &value, stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::all_almost_zero_mismatch(value) {
+            let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+            $crate::__value_details_assert_fail!("all_almost_zero", &value, stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::all_almost_zero!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let residuals = [0.1 + 0.2 - 0.3, 0.0, -1e-20];
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_all_almost_zero!(residuals);
+/// chek::debug_all_almost_zero!(&residuals[1..], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_all_almost_zero {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::all_almost_zero_mismatch(value) {
+                let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+                $crate::__value_details_assert_fail!("debug_all_almost_zero", &value, stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::all_almost_zero_mismatch(value) {
+                let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+                $crate::__value_details_assert_fail!("debug_all_almost_zero", &value, stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` unless every element of the slice is almost
+/// equal to zero, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::all_almost_zero!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_all_almost_zero!([0.1 + 0.2 - 0.3, 0.0]).is_ok());
+/// assert!(chek::try_all_almost_zero!([0.0, 1.0], "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_all_almost_zero {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::all_almost_zero_mismatch(value) {
+            Some(mismatch) => {
+                let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+                Err($crate::__internal::value_details_failure("all_almost_zero", &value, stringify!($value), &mismatch, None))
+            }
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::all_almost_zero_mismatch(value) {
+            Some(mismatch) => {
+                let value = $crate::__internal::slice_excerpt(value, mismatch.at());
+                Err($crate::__internal::value_details_failure("all_almost_zero", &value, stringify!($value), &mismatch,
+                                                              Some(format_args!($($msg_args)+))))
+            }
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function unless every
+/// element of the slice is almost equal to zero.
+///
+/// This is the `Result`-returning version of `chek::all_almost_zero!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_all_almost_zero!([0.1 + 0.2 - 0.3, 0.0]);
+///     chek::ensure_all_almost_zero!([0.0, 1.0]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_all_almost_zero!([0.0, 1.0], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_all_almost_zero {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_all_almost_zero!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_all_almost_zero!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
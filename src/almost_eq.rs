@@ -5,6 +5,18 @@
 /// See [the `almost::equal` documentation](https://docs.rs/almost/%2a/almost/fn.equal.html)
 /// for more details.
 ///
+/// Besides `f32` and `f64`, this accepts anything implementing
+/// `chek::AlmostEq`, such as arrays, slices, tuples and `Option`s of floats, and
+/// structs that `#[derive(chek::AlmostEq)]`. These are compared component by
+/// component, and the failure message gives the path to the first component
+/// that differed, e.g. `.rotation.w`.
+///
+/// A tolerance other than the default may be given as `tol = ...`, in which
+/// case [`almost::equal_with`](https://docs.rs/almost/%2a/almost/fn.equal_with.html)
+/// is used, and the failure message also shows how far apart the values are.
+/// For an absolute, relative or ULP tolerance with simpler semantics, see
+/// `chek::abs_diff_le!`, `chek::rel_diff_le!` and `chek::ulps_le!`.
+///
 /// A debug-only version is available as `chek::debug_almost_equal!`.
 ///
 /// Optionally may take an additional message to display on failure, which is
@@ -15,19 +27,37 @@
 /// ```rust
 /// chek::almost_equal!(4.0f32, 4.000001f32);
 /// chek::almost_equal!(4.0, 4.0, "should be equal");
+/// chek::almost_equal!(4.0, 4.0001, tol = 1e-4);
+/// chek::almost_equal!(4.0, 4.0001, tol = 1e-4, "should be equal");
 /// ```
 #[macro_export]
 macro_rules! almost_equal {
     ($left:expr, $right:expr $(,)?) => ({
-        let (left, right) = ($left, $right);
-        if !$crate::almost::equal(left, right) {
-            $crate::__cmp_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right));
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+            $crate::__details_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::MismatchDetails(&mismatch));
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr $(,)?) => ({
+        let (left, right, tol) = ($left, $right, $tol);
+        if !$crate::almost::equal_with(left, right, tol) {
+            $crate::__details_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "relative", tol));
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr, $($msg_args:tt)+) => ({
+        let (left, right, tol) = ($left, $right, $tol);
+        if !$crate::almost::equal_with(left, right, tol) {
+            $crate::__details_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "relative", tol), format_args!($($msg_args)+));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
-        let (left, right) = ($left, $right);
-        if !$crate::almost::equal(left, right) {
-            $crate::__cmp_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+            $crate::__details_assert_fail!("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::MismatchDetails(&mismatch), format_args!($($msg_args)+))
         }
     })
 }
@@ -45,35 +75,57 @@ macro_rules! almost_equal {
 /// // These are compiled to nothing if debug_assertions are off!
 /// chek::debug_almost_equal!(1.0f32, 1.0f32 + std::f32::EPSILON);
 /// chek::debug_almost_equal!(3.0, 3.000005f32, "Example message");
+/// chek::debug_almost_equal!(3.0, 3.0001, tol = 1e-4, "Example message");
 /// ```
 #[macro_export]
 macro_rules! debug_almost_equal {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
-            let (left, right) = ($left, $right);
-            if !$crate::almost::equal(left, right) {
-                $crate::__cmp_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right));
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+                $crate::__details_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::MismatchDetails(&mismatch));
+            }
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, tol) = ($left, $right, $tol);
+            if !$crate::almost::equal_with(left, right, tol) {
+                $crate::__details_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "relative", tol));
+            }
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, tol) = ($left, $right, $tol);
+            if !$crate::almost::equal_with(left, right, tol) {
+                $crate::__details_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "relative", tol), format_args!($($msg_args)+));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
-            let (left, right) = ($left, $right);
-            if !$crate::almost::equal(left, right) {
-                $crate::__cmp_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+                $crate::__details_assert_fail!("debug_almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::MismatchDetails(&mismatch), format_args!($($msg_args)+))
             }
         }
     })
 }
 
-// Note: The 'with_tolerance' versions are hard to use, so not provided for now...
-
 /// Panics if the provided values are almost equal to eachother.
 ///
 /// Uses the [`almost` crate](https://crates.io/crates/almost).
 /// See [the `almost::equal` documentation](https://docs.rs/almost/%2a/almost/fn.equal.html)
 /// for more details.
 ///
+/// Like `chek::almost_equal!`, this accepts anything implementing
+/// `chek::AlmostEq`.
+///
 /// A debug-only version is available as `chek::debug_not_almost_equal!`.
 ///
 /// Optionally may take an additional message to display on failure, which is
@@ -88,14 +140,14 @@ macro_rules! debug_almost_equal {
 #[macro_export]
 macro_rules! not_almost_equal {
     ($left:expr, $right:expr $(,)?) => ({
-        let (left, right) = ($left, $right);
-        if $crate::almost::equal(left, right) {
+        let (left, right) = (&($left), &($right));
+        if $crate::__internal::first_mismatch(left, right).is_none() {
             $crate::__cmp_assert_fail!("not_almost_equal", &left, &right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
-        let (left, right) = ($left, $right);
-        if $crate::almost::equal(left, right) {
+        let (left, right) = (&($left), &($right));
+        if $crate::__internal::first_mismatch(left, right).is_none() {
             $crate::__cmp_assert_fail!("not_almost_equal", &left, &right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
@@ -118,18 +170,217 @@ macro_rules! not_almost_equal {
 macro_rules! debug_not_almost_equal {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
-            let (left, right) = ($left, $right);
-            if $crate::almost::equal(left, right) {
+            let (left, right) = (&($left), &($right));
+            if $crate::__internal::first_mismatch(left, right).is_none() {
                 $crate::__cmp_assert_fail!("debug_not_almost_equal", &left, &right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
-            let (left, right) = ($left, $right);
-            if $crate::almost::equal(left, right) {
+            let (left, right) = (&($left), &($right));
+            if $crate::__internal::first_mismatch(left, right).is_none() {
                 $crate::__cmp_assert_fail!("debug_not_almost_equal", &left, &right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
 }
+
+/// Returns `Err(chek::Failure)` if the provided values are not almost equal to
+/// eachother, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::almost_equal!`. Uses the [`almost`
+/// crate](https://crates.io/crates/almost). See [the `almost::equal`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.equal.html) for more
+/// details.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_almost_equal!(4.0f32, 4.000001f32).is_ok());
+/// assert!(chek::try_almost_equal!(4.0, 5.0, "With a message").is_err());
+/// assert!(chek::try_almost_equal!(4.0, 4.001, tol = 1e-4, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+            Err($crate::__internal::details_failure("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::MismatchDetails(&mismatch), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr $(,)?) => ({
+        let (left, right, tol) = ($left, $right, $tol);
+        if !$crate::almost::equal_with(left, right, tol) {
+            Err($crate::__internal::details_failure("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "relative", tol), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr, $($msg_args:tt)+) => ({
+        let (left, right, tol) = ($left, $right, $tol);
+        if !$crate::almost::equal_with(left, right, tol) {
+            Err($crate::__internal::details_failure("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "relative", tol), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::first_mismatch(left, right) {
+            Err($crate::__internal::details_failure("almost_equal", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::MismatchDetails(&mismatch), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns `Err(chek::Failure)` if the provided values are almost equal to
+/// eachother, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::not_almost_equal!`. Uses the [`almost`
+/// crate](https://crates.io/crates/almost). See [the `almost::equal`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.equal.html) for more
+/// details.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_almost_equal!(4.0, 5.0).is_ok());
+/// assert!(chek::try_not_almost_equal!(4.0f32, 4.000001f32, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if $crate::__internal::first_mismatch(left, right).is_none() {
+            Err($crate::__internal::cmp_failure("not_almost_equal", &left, &right, stringify!($left), stringify!($right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if $crate::__internal::first_mismatch(left, right).is_none() {
+            Err($crate::__internal::cmp_failure("not_almost_equal", &left, &right, stringify!($left), stringify!($right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns early with an error from the enclosing function if the provided
+/// values are not almost equal to eachother.
+///
+/// This is the `Result`-returning version of `chek::almost_equal!`. Uses the
+/// [`almost` crate](https://crates.io/crates/almost). See [the `almost::equal`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.equal.html) for more
+/// details.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_almost_equal!(4.0f32, 4.000001f32);
+///     chek::ensure_almost_equal!(4.0, 5.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_almost_equal!(4.0, 4.0001, tol = 1e-4, "With an error");
+///     chek::ensure_almost_equal!(4.0, 5.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_almost_equal!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_almost_equal!($left, $right, tol = $tol) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, tol = $tol:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_almost_equal!($left, $right, tol = $tol) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_almost_equal!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the provided
+/// values are almost equal to eachother.
+///
+/// This is the `Result`-returning version of `chek::not_almost_equal!`. Uses
+/// the [`almost` crate](https://crates.io/crates/almost). See [the
+/// `almost::equal`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.equal.html) for more
+/// details.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_almost_equal!(4.0, 5.0);
+///     chek::ensure_not_almost_equal!(4.0f32, 4.000001f32);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_almost_equal!(4.0f32, 4.000001f32, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_almost_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_equal!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_equal!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
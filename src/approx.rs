@@ -0,0 +1,298 @@
+//! The `chek::AlmostEq` trait, which lets `chek::almost_equal!` compare
+//! structs, arrays and tuples of floats component by component.
+
+use core::fmt::{self, Debug};
+
+/// How many levels of a path `Mismatch` can hold. Past this, the outermost
+/// levels are left out.
+const MAX_DEPTH: usize = 16;
+
+/// Approximate equality, as checked by `chek::almost_equal!` (and its
+/// variants).
+///
+/// This is implemented for `f32` and `f64` using [`almost::equal`], and for
+/// references, slices, arrays, tuples and `Option`s by comparing each
+/// component. It can be derived for structs whose fields all implement it,
+/// with `#[derive(chek::AlmostEq)]`.
+///
+/// When a comparison fails, the returned `Mismatch` says which component
+/// differed, e.g. `.rotation.w` or `[2].0`.
+///
+/// [`almost::equal`]: https://docs.rs/almost/%2a/almost/fn.equal.html
+///
+/// # Example
+///
+/// ```rust
+/// use chek::AlmostEq;
+///
+/// #[derive(Debug, AlmostEq)]
+/// struct Vec3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// #[derive(Debug, AlmostEq)]
+/// struct Transform {
+///     translation: Vec3,
+///     rotation: [f32; 4],
+/// }
+///
+/// let a = Transform { translation: Vec3 { x: 1.0, y: 2.0, z: 3.0 }, rotation: [0.0, 0.0, 0.0, 1.0] };
+/// let b = Transform { translation: Vec3 { x: 1.0, y: 2.0, z: 3.0000001 }, rotation: [0.0, 0.0, 0.0, 1.0] };
+/// chek::almost_equal!(a, b);
+///
+/// let c = Transform { translation: Vec3 { x: 1.0, y: 2.5, z: 3.0 }, rotation: [0.0, 0.0, 0.0, 1.0] };
+/// let mismatch = a.first_mismatch(&c).unwrap();
+/// assert_eq!(mismatch.path().to_string(), ".translation.y");
+/// ```
+pub trait AlmostEq {
+    /// Returns `None` if `self` and `other` are almost equal, and otherwise the
+    /// first component that isn't.
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>>;
+
+    /// Returns `true` if `self` and `other` are almost equal.
+    #[inline]
+    fn almost_eq(&self, other: &Self) -> bool {
+        self.first_mismatch(other).is_none()
+    }
+}
+
+/// The first component that differed in a failed `AlmostEq` comparison, along
+/// with its path from the values being compared, e.g. `.rotation.w`.
+///
+/// Implementations of `AlmostEq` for aggregates add to the path as the
+/// mismatch is passed up, with `Mismatch::in_field` and `Mismatch::at_index`.
+#[derive(Clone, Copy)]
+pub struct Mismatch<'a> {
+    left: Leaf<'a>,
+    right: Leaf<'a>,
+    // Innermost first.
+    path: [Segment; MAX_DEPTH],
+    depth: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Leaf<'a> {
+    Value(&'a dyn Debug),
+    Len(usize),
+}
+
+#[derive(Clone, Copy)]
+enum Segment {
+    Field(&'static str),
+    Index(usize),
+    Len,
+}
+
+impl<'a> Mismatch<'a> {
+    /// A mismatch between `left` and `right` themselves, with an empty path.
+    #[inline]
+    pub fn new(left: &'a dyn Debug, right: &'a dyn Debug) -> Self {
+        Self::from_leaves(Leaf::Value(left), Leaf::Value(right))
+    }
+
+    /// A mismatch between the lengths of two sequences, with the path
+    /// `.len()`.
+    #[inline]
+    pub fn lengths(left: usize, right: usize) -> Self {
+        Self::from_leaves(Leaf::Len(left), Leaf::Len(right)).push(Segment::Len)
+    }
+
+    /// Adds a field (e.g. `rotation`, or `0` for a tuple) to the start of the
+    /// path.
+    #[inline]
+    pub fn in_field(self, name: &'static str) -> Self {
+        self.push(Segment::Field(name))
+    }
+
+    /// Adds an index to the start of the path.
+    #[inline]
+    pub fn at_index(self, index: usize) -> Self {
+        self.push(Segment::Index(index))
+    }
+
+    /// The path to the component that differed, which displays like
+    /// `.rotation.w`, or as nothing if the values themselves differed.
+    #[inline]
+    pub fn path(&self) -> MismatchPath<'_> {
+        MismatchPath(self)
+    }
+
+    /// The `Debug` output of the component on the left.
+    #[inline]
+    pub fn left(&self) -> &dyn Debug {
+        &self.left
+    }
+
+    /// The `Debug` output of the component on the right.
+    #[inline]
+    pub fn right(&self) -> &dyn Debug {
+        &self.right
+    }
+
+    fn from_leaves(left: Leaf<'a>, right: Leaf<'a>) -> Self {
+        Mismatch { left, right, path: [Segment::Len; MAX_DEPTH], depth: 0 }
+    }
+
+    fn push(mut self, segment: Segment) -> Self {
+        if let Some(slot) = self.path.get_mut(self.depth) {
+            *slot = segment;
+        }
+        self.depth += 1;
+        self
+    }
+}
+
+impl Debug for Mismatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mismatch")
+            .field("path", &format_args!("{}", self.path()))
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+impl Debug for Leaf<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Leaf::Value(value) => value.fmt(f),
+            Leaf::Len(len) => len.fmt(f),
+        }
+    }
+}
+
+/// The path to the component that differed in a `Mismatch`. See
+/// `Mismatch::path`.
+pub struct MismatchPath<'a>(&'a Mismatch<'a>);
+
+impl fmt::Display for MismatchPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Mismatch { path, depth, .. } = self.0;
+        if *depth > MAX_DEPTH {
+            f.write_str("...")?;
+        }
+        for segment in path[..(*depth).min(MAX_DEPTH)].iter().rev() {
+            match segment {
+                Segment::Field(name) => write!(f, ".{}", name)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+                Segment::Len => f.write_str(".len()")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+macro_rules! impl_float {
+    ($($f:ident),*) => {$(
+        impl AlmostEq for $f {
+            #[inline]
+            fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+                if almost::equal(*self, *other) {
+                    None
+                } else {
+                    Some(Mismatch::new(self, other))
+                }
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+impl<T: AlmostEq + ?Sized> AlmostEq for &T {
+    #[inline]
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+        (**self).first_mismatch(*other)
+    }
+}
+
+impl<T: AlmostEq + ?Sized> AlmostEq for &mut T {
+    #[inline]
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+        (**self).first_mismatch(*other)
+    }
+}
+
+impl<T: AlmostEq> AlmostEq for [T] {
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+        if self.len() != other.len() {
+            return Some(Mismatch::lengths(self.len(), other.len()));
+        }
+        let mut pairs = self.iter().zip(other).enumerate();
+        pairs.find_map(|(i, (a, b))| Some(a.first_mismatch(b)?.at_index(i)))
+    }
+}
+
+impl<T: AlmostEq, const N: usize> AlmostEq for [T; N] {
+    #[inline]
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+        self[..].first_mismatch(&other[..])
+    }
+}
+
+/// `Some` and `None` never match each other. Two `Some`s are compared by their
+/// contents, without adding to the path.
+impl<T: AlmostEq + Debug> AlmostEq for Option<T> {
+    fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+        match (self, other) {
+            (Some(a), Some(b)) => a.first_mismatch(b),
+            (None, None) => None,
+            _ => Some(Mismatch::new(self, other)),
+        }
+    }
+}
+
+macro_rules! impl_tuple {
+    ($(($($name:ident $index:tt),+))+) => {$(
+        impl<$($name: AlmostEq),+> AlmostEq for ($($name,)+) {
+            fn first_mismatch<'a>(&'a self, other: &'a Self) -> Option<Mismatch<'a>> {
+                $(
+                    if let Some(mismatch) = self.$index.first_mismatch(&other.$index) {
+                        return Some(mismatch.in_field(stringify!($index)));
+                    }
+                )+
+                None
+            }
+        }
+    )+};
+}
+
+impl_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11)
+}
+
+/// Used by `chek::almost_equal!` and friends. A free function, so that float
+/// literals fall back to `f64` as usual.
+#[doc(hidden)]
+#[inline]
+pub fn first_mismatch<'a, T: AlmostEq + ?Sized>(left: &'a T, right: &'a T) -> Option<Mismatch<'a>> {
+    left.first_mismatch(right)
+}
+
+/// The `result:` line of a failed `chek::almost_equal!`, if the values were
+/// aggregates. Formats as nothing if the values themselves differed.
+#[doc(hidden)]
+pub struct MismatchDetails<'a>(pub &'a Mismatch<'a>);
+
+impl fmt::Display for MismatchDetails<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mismatch = self.0;
+        if mismatch.depth == 0 {
+            return Ok(());
+        }
+        write!(f, "\nresult: `{}` differs: `{:?}` vs `{:?}`", mismatch.path(), mismatch.left, mismatch.right)
+    }
+}
@@ -0,0 +1,178 @@
+
+/// Panics if the two byte buffers are not equal, printing a hex dump of both.
+///
+/// Accepts anything implementing `AsRef<[u8]>`, e.g. `&[u8]`, `[u8; N]`,
+/// `Vec<u8>` or `&str`. On failure, the message gives the offset of the first
+/// differing byte and both lengths, followed by an `xxd`-style hex and ASCII
+/// dump of the two buffers side by side, starting just before the first
+/// difference, with every differing byte marked. This doesn't allocate.
+///
+/// A debug-only version is available as `chek::debug_bytes_eq!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let frame = vec![0x47, 0x45, 0x54, 0x20];
+/// chek::bytes_eq!(frame, b"GET ");
+/// chek::bytes_eq!(&frame[..3], "GET", "With a message");
+/// chek::bytes_eq!(frame, [0x47, 0x45, 0x54, 0x20], "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let frame = b"GET /index.html\r\n".to_vec();
+/// // assertion failed: `chek::bytes_eq!(left, right)`
+/// //   left: `<17 bytes>` = `frame`,
+/// //  right: `<15 bytes>` = `b"GET /index.htm\n"`
+/// // result: first difference at offset 14 (0xe), lengths are 17 and 15
+/// // 00000000: 47 45 54 20 2f 69 6e 64  GET /ind | 47 45 54 20 2f 69 6e 64  GET /ind
+/// // 00000008: 65 78 2e 68 74 6d 6c 0d  ex.html. | 65 78 2e 68 74 6d 0a     ex.htm.
+/// //                             ^^ ^^        ^^                     ^^ ^^        ^^
+/// // 00000010: 0a                       .        |
+/// //           ^^                       ^          ^^                       ^
+/// chek::bytes_eq!(frame, b"GET /index.htm\n");
+/// ```
+#[macro_export]
+macro_rules! bytes_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+        if let Some(mismatch) = $crate::__internal::bytes_mismatch(left, right) {
+            $crate::__bytes_assert_fail!("bytes_eq", left, right, stringify!($left), stringify!($right), mismatch);
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+        if let Some(mismatch) = $crate::__internal::bytes_mismatch(left, right) {
+            $crate::__bytes_assert_fail!("bytes_eq", left, right, stringify!($left), stringify!($right), mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::bytes_eq!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let frame = vec![0x47, 0x45, 0x54, 0x20];
+/// chek::debug_bytes_eq!(frame, b"GET ");
+/// chek::debug_bytes_eq!(frame, b"GET ", "With a message");
+/// chek::debug_bytes_eq!(frame, b"GET ", "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_bytes_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+            if let Some(mismatch) = $crate::__internal::bytes_mismatch(left, right) {
+                    $crate::__bytes_assert_fail!("debug_bytes_eq", left, right, stringify!($left), stringify!($right), mismatch);
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+            if let Some(mismatch) = $crate::__internal::bytes_mismatch(left, right) {
+                    $crate::__bytes_assert_fail!("debug_bytes_eq", left, right, stringify!($left), stringify!($right), mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two byte buffers are not equal, and
+/// `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::bytes_eq!`. Accepts anything
+/// implementing `AsRef<[u8]>`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_bytes_eq!(b"abc", vec![b'a', b'b', b'c']).is_ok());
+/// assert!(chek::try_bytes_eq!(b"abc", "ab").is_err());
+/// assert!(chek::try_bytes_eq!(b"abc", "ab", "With a message").is_err());
+/// assert!(chek::try_bytes_eq!(b"abc", "ab", "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_bytes_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+        match $crate::__internal::bytes_mismatch(left, right) {
+            Some(mismatch) => {
+                    Err($crate::__internal::bytes_failure("bytes_eq", left, right, stringify!($left), stringify!($right), mismatch, None))
+            }
+            None => Ok(()),
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        let (left, right) = ($crate::__internal::as_bytes(left), $crate::__internal::as_bytes(right));
+        match $crate::__internal::bytes_mismatch(left, right) {
+            Some(mismatch) => {
+                    Err($crate::__internal::bytes_failure("bytes_eq", left, right, stringify!($left), stringify!($right), mismatch, Some(format_args!($($msg_args)+))))
+            }
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two byte
+/// buffers are not equal.
+///
+/// This is the `Result`-returning version of `chek::bytes_eq!`. Accepts
+/// anything implementing `AsRef<[u8]>`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_bytes_eq!(b"ab", "ab");
+///     chek::ensure_bytes_eq!(b"ab", "ac");
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_bytes_eq!(b"ab", "ac", "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_bytes_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_bytes_eq!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_bytes_eq!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
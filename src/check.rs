@@ -0,0 +1,149 @@
+
+/// Panics if the provided boolean expression is false, printing the value of
+/// each of its subexpressions.
+///
+/// This is a "power assert": the expression is broken down along `&&`, `||`,
+/// `!`, the comparison and arithmetic operators, and function and method calls,
+/// and the value of each operand is recorded as it's evaluated. On failure, the
+/// source text and value of every recorded subexpression is printed.
+/// Subexpressions that were skipped due to short-circuiting are reported as
+/// such.
+///
+/// Requires that the operands of comparisons, and the other values of type
+/// `bool`, implement `Debug`. Comparisons still just use
+/// `PartialEq`/`PartialOrd`, same as if you wrote them without `check!`. The
+/// receivers and arguments of calls, and the operands of arithmetic, are only
+/// recorded if they're `Copy` and `Debug` (which includes references like
+/// `&x`), since recording them otherwise would change what the call does.
+///
+/// A debug-only version is available as `chek::debug_check!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// Note: A subexpression that borrows from a temporary on the right hand side
+/// of `&&` or `||` (for example `x && make_string().as_str() == "a"`) may fail
+/// to compile, as the temporary won't live long enough to be reported. Binding
+/// it to a local first will fix this.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 2, 3];
+/// let (a, b) = (4, 4);
+/// chek::check!(v.len() < a && a == b);
+/// chek::check!(!v.is_empty() || v.len() == 100, "With a message");
+/// chek::check!(v[0] != 3, "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let v = vec![1, 2, 3];
+/// let (a, b) = (4, 5);
+/// // assertion failed: `chek::check!(v.len() < a && a == b)`
+/// //   v.len(): `3`
+/// //         a: `4`
+/// //         a: `4`
+/// //         b: `5`
+/// chek::check!(v.len() < a && a == b);
+/// ```
+#[macro_export]
+macro_rules! check {
+    ($($args:tt)+) => {
+        $crate::__check!($crate, "check", $($args)+)
+    };
+}
+
+/// Same as `chek::check!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// let v = vec![1, 2, 3];
+/// chek::debug_check!(v.len() == 3 && v[2] > v[1]);
+/// chek::debug_check!(v.contains(&2), "With a message");
+/// chek::debug_check!(!v.is_empty(), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_check {
+    ($($args:tt)+) => {
+        if cfg!(debug_assertions) {
+            $crate::__check!($crate, "debug_check", $($args)+)
+        }
+    };
+}
+
+/// Returns `Err(chek::Failure)` if the provided boolean expression is false,
+/// and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::check!`. The `Failure` records
+/// the value of each subexpression, as `chek::check!` would print them.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 2, 3];
+/// assert!(chek::try_check!(v.len() == 3 && v[0] < v[1]).is_ok());
+/// assert!(chek::try_check!(v.is_empty()).is_err());
+/// assert!(chek::try_check!(v[0] > 2, "With a message").is_err());
+/// assert!(chek::try_check!(v[0] > 2, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_check {
+    ($($args:tt)+) => {
+        $crate::__check!($crate, try "check", $($args)+)
+    };
+}
+
+/// Returns early with an error from the enclosing function if the provided
+/// boolean expression is false.
+///
+/// This is the `Result`-returning version of `chek::check!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(v: &[i32]) -> Result<(), chek::Failure> {
+///     chek::ensure_check!(v.len() == 3 && v[0] < v[1]);
+///     Ok(())
+/// }
+///
+/// fn with_error(v: &[i32]) -> Result<(), &'static str> {
+///     chek::ensure_check!(!v.is_empty(), "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error(&[1, 2, 3]).is_ok());
+/// assert!(no_error(&[2, 1, 3]).is_err());
+/// assert_eq!(with_error(&[]), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_check {
+    ($cond:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_check!($cond) {
+            return Err(From::from(failure));
+        }
+    });
+    ($cond:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_check!($cond) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,770 @@
+
+/// Panics if the float is infinite or NaN. If given a slice or array of floats,
+/// panics if any of its elements is.
+///
+/// On failure, the message says what the (first offending) float was instead,
+/// e.g. "was NaN" or "was subnormal", along with its bit pattern.
+///
+/// A debug-only version is available as `chek::debug_finite!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::finite!(1.0f32);
+/// chek::finite!([0.0, -1e300, f64::MIN_POSITIVE], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let x = 0.0f32 / 0.0;
+/// // assertion failed: `chek::finite!(value)`
+/// //  value: `NaN` = `x`
+/// // result: was NaN (bits: `0x7fc00000`)
+/// chek::finite!(x);
+/// ```
+#[macro_export]
+macro_rules! finite {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+            $crate::__value_details_assert_fail!("finite", &mismatch.value(), stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+            $crate::__value_details_assert_fail!("finite", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::finite!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_finite!(1.0f32);
+/// chek::debug_finite!([0.0, -1e300, f64::MIN_POSITIVE], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_finite {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+                $crate::__value_details_assert_fail!("debug_finite", &mismatch.value(), stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+                $crate::__value_details_assert_fail!("debug_finite", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if `chek::finite!` would fail, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::finite!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_finite!(1.0f32).is_ok());
+/// assert!(chek::try_finite!(f64::INFINITY, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_finite {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("finite", &mismatch.value(), stringify!($value), &mismatch, None)),
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Finite) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("finite", &mismatch.value(), stringify!($value), &mismatch,
+                                                                            Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if `chek::finite!`
+/// would fail.
+///
+/// This is the `Result`-returning version of `chek::finite!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_finite!(1.0f32);
+///     chek::ensure_finite!(f64::INFINITY);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_finite!(f64::INFINITY, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_finite {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_finite!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_finite!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics if the float is NaN. If given a slice or array of floats, panics if
+/// any of its elements is.
+///
+/// On failure, the message says what the (first offending) float was instead,
+/// e.g. "was NaN" or "was subnormal", along with its bit pattern.
+///
+/// A debug-only version is available as `chek::debug_not_nan!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::not_nan!(1.0f32);
+/// chek::not_nan!([0.0, f64::INFINITY, -f64::INFINITY], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let state = [1.0f32, 2.0, f32::NAN, 4.0];
+/// // assertion failed: `chek::not_nan!(value)`
+/// //  value: `[1.0, 2.0, NaN, 4.0]` = `state`
+/// // result: element 2 was NaN (bits: `0x7fc00000`)
+/// chek::not_nan!(state);
+/// ```
+#[macro_export]
+macro_rules! not_nan {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+            $crate::__value_details_assert_fail!("not_nan", &mismatch.value(), stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+            $crate::__value_details_assert_fail!("not_nan", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::not_nan!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_not_nan!(1.0f32);
+/// chek::debug_not_nan!([0.0, f64::INFINITY, -f64::INFINITY], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_not_nan {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+                $crate::__value_details_assert_fail!("debug_not_nan", &mismatch.value(), stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+                $crate::__value_details_assert_fail!("debug_not_nan", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if `chek::not_nan!` would fail, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::not_nan!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_nan!(1.0f32).is_ok());
+/// assert!(chek::try_not_nan!(f64::NAN, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_nan {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("not_nan", &mismatch.value(), stringify!($value), &mismatch, None)),
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::NotNan) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("not_nan", &mismatch.value(), stringify!($value), &mismatch,
+                                                                            Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if `chek::not_nan!`
+/// would fail.
+///
+/// This is the `Result`-returning version of `chek::not_nan!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_nan!(1.0f32);
+///     chek::ensure_not_nan!(f64::NAN);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_nan!(f64::NAN, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_nan {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_nan!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_nan!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the float is normal, that is, neither zero, subnormal, infinite
+/// nor NaN. If given a slice or array of floats, panics unless all of its
+/// elements are.
+///
+/// On failure, the message says what the (first offending) float was instead,
+/// e.g. "was NaN" or "was subnormal", along with its bit pattern.
+///
+/// A debug-only version is available as `chek::debug_normal!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::normal!(1.0f32);
+/// chek::normal!([-1.0, f64::MIN_POSITIVE, f64::MAX], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let x = f32::MIN_POSITIVE / 2.0;
+/// // assertion failed: `chek::normal!(value)`
+/// //  value: `5.877472e-39` = `x`
+/// // result: was subnormal (bits: `0x00400000`)
+/// chek::normal!(x);
+/// ```
+#[macro_export]
+macro_rules! normal {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+            $crate::__value_details_assert_fail!("normal", &mismatch.value(), stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+            $crate::__value_details_assert_fail!("normal", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::normal!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_normal!(1.0f32);
+/// chek::debug_normal!([-1.0, f64::MIN_POSITIVE, f64::MAX], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_normal {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+                $crate::__value_details_assert_fail!("debug_normal", &mismatch.value(), stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+                $crate::__value_details_assert_fail!("debug_normal", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if `chek::normal!` would fail, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::normal!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_normal!(1.0f32).is_ok());
+/// assert!(chek::try_normal!(0.0, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_normal {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("normal", &mismatch.value(), stringify!($value), &mismatch, None)),
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::Normal) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("normal", &mismatch.value(), stringify!($value), &mismatch,
+                                                                            Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if `chek::normal!`
+/// would fail.
+///
+/// This is the `Result`-returning version of `chek::normal!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_normal!(1.0f32);
+///     chek::ensure_normal!(0.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_normal!(0.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_normal {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_normal!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_normal!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the float has a positive sign, as checked by
+/// `f64::is_sign_positive`. Note that this includes `0.0`, `+inf` and NaNs
+/// without the sign bit set, but not `-0.0`. If given a slice or array of
+/// floats, panics unless all of its elements have a positive sign.
+///
+/// On failure, the message says what the (first offending) float was instead,
+/// e.g. "was NaN" or "was subnormal", along with its bit pattern.
+///
+/// A debug-only version is available as `chek::debug_sign_positive!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::sign_positive!(1.0f32);
+/// chek::sign_positive!([0.0, f64::INFINITY, f64::MIN_POSITIVE], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let x = -f64::INFINITY;
+/// // assertion failed: `chek::sign_positive!(value)`
+/// //  value: `-inf` = `x`
+/// // result: was -inf (bits: `0xfff0000000000000`)
+/// chek::sign_positive!(x);
+/// ```
+#[macro_export]
+macro_rules! sign_positive {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+            $crate::__value_details_assert_fail!("sign_positive", &mismatch.value(), stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+            $crate::__value_details_assert_fail!("sign_positive", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::sign_positive!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_sign_positive!(1.0f32);
+/// chek::debug_sign_positive!([0.0, f64::INFINITY, f64::MIN_POSITIVE], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_sign_positive {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+                $crate::__value_details_assert_fail!("debug_sign_positive", &mismatch.value(), stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+                $crate::__value_details_assert_fail!("debug_sign_positive", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if `chek::sign_positive!` would fail, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::sign_positive!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_sign_positive!(1.0f32).is_ok());
+/// assert!(chek::try_sign_positive!(-0.0, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_sign_positive {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("sign_positive", &mismatch.value(), stringify!($value), &mismatch, None)),
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignPositive) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("sign_positive", &mismatch.value(), stringify!($value), &mismatch,
+                                                                            Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if `chek::sign_positive!`
+/// would fail.
+///
+/// This is the `Result`-returning version of `chek::sign_positive!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_sign_positive!(1.0f32);
+///     chek::ensure_sign_positive!(-0.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_sign_positive!(-0.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_sign_positive {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sign_positive!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sign_positive!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the float has a negative sign, as checked by
+/// `f64::is_sign_negative`. Note that this includes `-0.0`, `-inf` and NaNs
+/// with the sign bit set, but not `0.0`. If given a slice or array of floats,
+/// panics unless all of its elements have a negative sign.
+///
+/// On failure, the message says what the (first offending) float was instead,
+/// e.g. "was NaN" or "was subnormal", along with its bit pattern.
+///
+/// A debug-only version is available as `chek::debug_sign_negative!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::sign_negative!(-1.0f32);
+/// chek::sign_negative!([-0.0, -f64::INFINITY, -f64::MIN_POSITIVE], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let x = 0.0f32;
+/// // assertion failed: `chek::sign_negative!(value)`
+/// //  value: `0.0` = `x`
+/// // result: was zero (bits: `0x00000000`)
+/// chek::sign_negative!(x);
+/// ```
+#[macro_export]
+macro_rules! sign_negative {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+            $crate::__value_details_assert_fail!("sign_negative", &mismatch.value(), stringify!($value), &mismatch);
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+            $crate::__value_details_assert_fail!("sign_negative", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::sign_negative!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_sign_negative!(-1.0f32);
+/// chek::debug_sign_negative!([-0.0, -f64::INFINITY, -f64::MIN_POSITIVE], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_sign_negative {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+                $crate::__value_details_assert_fail!("debug_sign_negative", &mismatch.value(), stringify!($value), &mismatch);
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let value = &($value);
+            if let Some(mismatch) = $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+                $crate::__value_details_assert_fail!("debug_sign_negative", &mismatch.value(), stringify!($value), &mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if `chek::sign_negative!` would fail, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::sign_negative!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_sign_negative!(-1.0f32).is_ok());
+/// assert!(chek::try_sign_negative!(0.0, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_sign_negative {
+    ($value:expr $(,)?) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("sign_negative", &mismatch.value(), stringify!($value), &mismatch, None)),
+            None => Ok(()),
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = &($value);
+        match $crate::__internal::class_mismatch(value, $crate::__internal::FloatClass::SignNegative) {
+            Some(mismatch) => Err($crate::__internal::value_details_failure("sign_negative", &mismatch.value(), stringify!($value), &mismatch,
+                                                                            Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if `chek::sign_negative!`
+/// would fail.
+///
+/// This is the `Result`-returning version of `chek::sign_negative!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_sign_negative!(-1.0f32);
+///     chek::ensure_sign_negative!(0.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_sign_negative!(0.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_sign_negative {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sign_negative!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sign_negative!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
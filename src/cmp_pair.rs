@@ -0,0 +1,160 @@
+//! How the comparison assertions (e.g. `chek::lt!` and `chek::eq!`) decide
+//! which trait to compare their operands with.
+//!
+//! The operands are wrapped in a `CmpPair`, and each way of comparing them is a
+//! trait implemented for a different number of references to it, with bounds
+//! that only hold if that way works. `chek::__cmp!` calls the method on
+//! `&&&&&CmpPair`, and method resolution picks the implementation with the most
+//! references whose bounds hold (the "autoref specialization" trick). From
+//! most to least preferred:
+//!
+//! 1. `PartialOrd` or `PartialEq` between the operands themselves.
+//! 2. `chek::IntCmp`, for primitive integers of different types.
+//! 3. `PartialEq` after dereferencing the left operand, e.g. `Box<T>` vs `T`.
+//! 4. `PartialEq` after dereferencing the right operand, e.g. `[T; N]` vs
+//!    `Vec<T>`.
+//! 5. `PartialEq` after dereferencing both, e.g. `Box<str>` vs `&str`.
+//!
+//! The standard traits come first so that they still drive type inference,
+//! e.g. in `chek::lt!(x, 5_000_000_000)` with `x: u64`, the literal is still a
+//! `u64`. `IntCmp` would leave it ambiguous, so it would fall back to `i32`,
+//! and not fit.
+
+use core::cmp::Ordering;
+use core::ops::Deref;
+
+use crate::IntCmp;
+
+#[doc(hidden)]
+pub struct CmpPair<'a, A: ?Sized, B: ?Sized>(pub &'a A, pub &'a B);
+
+#[doc(hidden)]
+pub trait PartialOrdPair {
+    fn is_lt(&self) -> bool;
+    fn is_le(&self) -> bool;
+    fn is_gt(&self) -> bool;
+    fn is_ge(&self) -> bool;
+    fn ordering(&self) -> Option<Ordering>;
+}
+
+impl<A: PartialOrd<B> + ?Sized, B: ?Sized> PartialOrdPair for &&&&CmpPair<'_, A, B> {
+    #[inline]
+    fn is_lt(&self) -> bool {
+        self.0 < self.1
+    }
+    #[inline]
+    fn is_le(&self) -> bool {
+        self.0 <= self.1
+    }
+    #[inline]
+    fn is_gt(&self) -> bool {
+        self.0 > self.1
+    }
+    #[inline]
+    fn is_ge(&self) -> bool {
+        self.0 >= self.1
+    }
+    #[inline]
+    fn ordering(&self) -> Option<Ordering> {
+        self.0.partial_cmp(self.1)
+    }
+}
+
+#[doc(hidden)]
+pub trait PartialEqPair {
+    fn is_eq(&self) -> bool;
+}
+
+impl<A: PartialEq<B> + ?Sized, B: ?Sized> PartialEqPair for &&&&CmpPair<'_, A, B> {
+    #[inline]
+    fn is_eq(&self) -> bool {
+        self.0 == self.1
+    }
+}
+
+#[doc(hidden)]
+pub trait IntCmpPair {
+    fn is_lt(&self) -> bool;
+    fn is_le(&self) -> bool;
+    fn is_gt(&self) -> bool;
+    fn is_ge(&self) -> bool;
+    fn is_eq(&self) -> bool;
+    fn ordering(&self) -> Option<Ordering>;
+}
+
+impl<A: IntCmp<B> + ?Sized, B: ?Sized> IntCmpPair for &&&CmpPair<'_, A, B> {
+    #[inline]
+    fn is_lt(&self) -> bool {
+        self.0.int_cmp(self.1).is_lt()
+    }
+    #[inline]
+    fn is_le(&self) -> bool {
+        self.0.int_cmp(self.1).is_le()
+    }
+    #[inline]
+    fn is_gt(&self) -> bool {
+        self.0.int_cmp(self.1).is_gt()
+    }
+    #[inline]
+    fn is_ge(&self) -> bool {
+        self.0.int_cmp(self.1).is_ge()
+    }
+    #[inline]
+    fn is_eq(&self) -> bool {
+        self.0.int_cmp(self.1).is_eq()
+    }
+    #[inline]
+    fn ordering(&self) -> Option<Ordering> {
+        Some(self.0.int_cmp(self.1))
+    }
+}
+
+#[doc(hidden)]
+pub trait DerefLeftEqPair {
+    fn is_eq(&self) -> bool;
+}
+
+impl<A, B> DerefLeftEqPair for &&CmpPair<'_, A, B>
+where
+    A: Deref + ?Sized,
+    A::Target: PartialEq<B>,
+    B: ?Sized,
+{
+    #[inline]
+    fn is_eq(&self) -> bool {
+        **self.0 == *self.1
+    }
+}
+
+#[doc(hidden)]
+pub trait DerefRightEqPair {
+    fn is_eq(&self) -> bool;
+}
+
+impl<A, B> DerefRightEqPair for &CmpPair<'_, A, B>
+where
+    A: PartialEq<B::Target> + ?Sized,
+    B: Deref + ?Sized,
+{
+    #[inline]
+    fn is_eq(&self) -> bool {
+        *self.0 == **self.1
+    }
+}
+
+#[doc(hidden)]
+pub trait DerefBothEqPair {
+    fn is_eq(&self) -> bool;
+}
+
+impl<A, B> DerefBothEqPair for CmpPair<'_, A, B>
+where
+    A: Deref + ?Sized,
+    A::Target: PartialEq<B::Target>,
+    B: Deref + ?Sized,
+{
+    #[inline]
+    fn is_eq(&self) -> bool {
+        **self.0 == **self.1
+    }
+}
@@ -0,0 +1,386 @@
+//! The `chek::Contains` trait, which lets `chek::contains!` (and its variants)
+//! look for elements of slices, strings, maps, sets and other collections.
+
+use core::fmt::{self, Debug, Write};
+use core::ops::Deref;
+
+/// Membership, as checked by `chek::contains!` and `chek::not_contains!` (and
+/// their variants).
+///
+/// This is implemented for:
+///
+/// - Slices and arrays, for any needle their elements can be compared to with
+///   `PartialEq`.
+/// - `str`, for substrings and `char`s.
+/// - `BTreeMap` and `HashMap`, for keys, and `BTreeSet` and `HashSet`, for
+///   elements. As with `contains_key` and `contains`, the needle may be any
+///   type the keys can be borrowed as. These require the `alloc` and `std`
+///   features respectively.
+/// - References to any of these.
+///
+/// The assertions also accept anything that dereferences to one of these, e.g.
+/// a `Vec` or `String`, and needles that only work once they're dereferenced,
+/// e.g. a `&str` for a `HashSet<String>`.
+///
+/// # Example
+///
+/// ```rust
+/// use chek::{Contains, Location};
+///
+/// struct Ring([u8; 4]);
+///
+/// impl Contains<u8> for Ring {
+///     fn locate(&self, needle: &u8) -> Option<Location<'_>> {
+///         self.0.iter().position(|x| x == needle).map(Location::Index)
+///     }
+///
+///     fn len(&self) -> usize {
+///         self.0.len()
+///     }
+/// }
+///
+/// let ring = Ring([3, 1, 4, 1]);
+/// assert!(matches!(ring.locate(&1), Some(Location::Index(1))));
+/// chek::contains!(ring, 4);
+/// chek::not_contains!(ring, 5);
+/// ```
+pub trait Contains<Needle: ?Sized> {
+    /// Returns where `needle` is in `self`, or `None` if it isn't there.
+    fn locate(&self, needle: &Needle) -> Option<Location<'_>>;
+
+    /// The number of elements in `self` (for strings, bytes), which is shown
+    /// on failure.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if `self` has no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Where a needle was found by `Contains::locate`, which `chek::not_contains!`
+/// reports on failure.
+#[derive(Clone, Copy, Debug)]
+pub enum Location<'a> {
+    /// At this index. For strings, this is a byte offset.
+    Index(usize),
+    /// Under this key of a map.
+    Key(&'a dyn Debug),
+    /// As this element of a set.
+    Element(&'a dyn Debug),
+}
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Index(index) => write!(f, "at index {}", index),
+            Location::Key(key) => write!(f, "under the key `{:?}`", key),
+            Location::Element(element) => write!(f, "as the element `{:?}`", element),
+        }
+    }
+}
+
+impl<H: Contains<N> + ?Sized, N: ?Sized> Contains<N> for &H {
+    #[inline]
+    fn locate(&self, needle: &N) -> Option<Location<'_>> {
+        (**self).locate(needle)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        Contains::<N>::len(&**self)
+    }
+}
+
+impl<H: Contains<N> + ?Sized, N: ?Sized> Contains<N> for &mut H {
+    #[inline]
+    fn locate(&self, needle: &N) -> Option<Location<'_>> {
+        (**self).locate(needle)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        Contains::<N>::len(&**self)
+    }
+}
+
+impl<T: PartialEq<N>, N> Contains<N> for [T] {
+    #[inline]
+    fn locate(&self, needle: &N) -> Option<Location<'_>> {
+        self.iter().position(|x| x == needle).map(Location::Index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: PartialEq<N>, N, const M: usize> Contains<N> for [T; M] {
+    #[inline]
+    fn locate(&self, needle: &N) -> Option<Location<'_>> {
+        self[..].locate(needle)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        M
+    }
+}
+
+impl Contains<str> for str {
+    #[inline]
+    fn locate(&self, needle: &str) -> Option<Location<'_>> {
+        self.find(needle).map(Location::Index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Contains<char> for str {
+    #[inline]
+    fn locate(&self, needle: &char) -> Option<Location<'_>> {
+        self.find(*needle).map(Location::Index)
+    }
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod alloc_impls {
+    use super::{Contains, Location};
+    use alloc::collections::{BTreeMap, BTreeSet};
+    use core::borrow::Borrow;
+    use core::fmt::Debug;
+
+    impl<K: Borrow<Q> + Ord + Debug, V, Q: Ord + ?Sized> Contains<Q> for BTreeMap<K, V> {
+        #[inline]
+        fn locate(&self, needle: &Q) -> Option<Location<'_>> {
+            self.get_key_value(needle).map(|(key, _)| Location::Key(key))
+        }
+        #[inline]
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T: Borrow<Q> + Ord + Debug, Q: Ord + ?Sized> Contains<Q> for BTreeSet<T> {
+        #[inline]
+        fn locate(&self, needle: &Q) -> Option<Location<'_>> {
+            self.get(needle).map(|element| Location::Element(element))
+        }
+        #[inline]
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<K, V> super::Map for BTreeMap<K, V> {}
+}
+
+#[cfg(feature = "std")]
+mod std_impls {
+    use super::{Contains, Location};
+    use core::borrow::Borrow;
+    use core::fmt::Debug;
+    use core::hash::{BuildHasher, Hash};
+    use std::collections::{HashMap, HashSet};
+
+    impl<K, V, Q, S> Contains<Q> for HashMap<K, V, S>
+    where
+        K: Borrow<Q> + Hash + Eq + Debug,
+        Q: Hash + Eq + ?Sized,
+        S: BuildHasher,
+    {
+        #[inline]
+        fn locate(&self, needle: &Q) -> Option<Location<'_>> {
+            self.get_key_value(needle).map(|(key, _)| Location::Key(key))
+        }
+        #[inline]
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<T, Q, S> Contains<Q> for HashSet<T, S>
+    where
+        T: Borrow<Q> + Hash + Eq + Debug,
+        Q: Hash + Eq + ?Sized,
+        S: BuildHasher,
+    {
+        #[inline]
+        fn locate(&self, needle: &Q) -> Option<Location<'_>> {
+            self.get(needle).map(|element| Location::Element(element))
+        }
+        #[inline]
+        fn len(&self) -> usize {
+            self.len()
+        }
+    }
+
+    impl<K, V, S> super::Map for HashMap<K, V, S> {}
+}
+
+/// The maps that `chek::contains_key!` accepts.
+#[doc(hidden)]
+pub trait Map {}
+
+impl<M: Map + ?Sized> Map for &M {}
+
+impl<M: Map + ?Sized> Map for &mut M {}
+
+/// Used by `chek::contains_key!` to reject anything that isn't a map.
+#[doc(hidden)]
+#[inline(always)]
+pub fn as_map<M: Map + ?Sized>(map: &M) -> &M {
+    map
+}
+
+/// The result of looking for a needle, as determined by `chek::__contains!`.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct ContainsOutcome<'a> {
+    len: usize,
+    found: Option<Location<'a>>,
+}
+
+impl ContainsOutcome<'_> {
+    #[inline]
+    pub fn is_found(&self) -> bool {
+        self.found.is_some()
+    }
+}
+
+impl fmt::Display for ContainsOutcome<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.found {
+            Some(location) => write!(f, "\nresult: left (length {}) contains right {}", self.len, location),
+            None => write!(f, "\nresult: left (length {}) does not contain right", self.len),
+        }
+    }
+}
+
+/// A haystack and a needle. `chek::__contains!` calls `outcome` on
+/// `&&&&NeedlePair`, and method resolution picks the first of these that
+/// applies (the same "autoref specialization" trick as in `cmp_pair.rs`):
+///
+/// 1. The haystack implements `Contains` for the needle.
+/// 2. It does for what the needle dereferences to, e.g. `str` for `&str`.
+/// 3. What the haystack dereferences to does for the needle, e.g. `[T]` for
+///    `Vec<T>`.
+/// 4. What the haystack dereferences to does for what the needle dereferences
+///    to, e.g. `String` and `&str`.
+#[doc(hidden)]
+pub struct NeedlePair<'a, H: ?Sized, N: ?Sized>(pub &'a H, pub &'a N);
+
+#[doc(hidden)]
+pub trait ContainsNeedle<'a> {
+    fn outcome(&self) -> ContainsOutcome<'a>;
+}
+
+impl<'a, H: Contains<N> + ?Sized, N: ?Sized> ContainsNeedle<'a> for &&&NeedlePair<'a, H, N> {
+    #[inline]
+    fn outcome(&self) -> ContainsOutcome<'a> {
+        outcome(self.0, self.1)
+    }
+}
+
+#[doc(hidden)]
+pub trait ContainsDerefNeedle<'a> {
+    fn outcome(&self) -> ContainsOutcome<'a>;
+}
+
+impl<'a, H, N> ContainsDerefNeedle<'a> for &&NeedlePair<'a, H, N>
+where
+    H: Contains<N::Target> + ?Sized,
+    N: Deref + ?Sized,
+{
+    #[inline]
+    fn outcome(&self) -> ContainsOutcome<'a> {
+        outcome(self.0, &**self.1)
+    }
+}
+
+#[doc(hidden)]
+pub trait DerefContainsNeedle<'a> {
+    fn outcome(&self) -> ContainsOutcome<'a>;
+}
+
+impl<'a, H, N> DerefContainsNeedle<'a> for &NeedlePair<'a, H, N>
+where
+    H: Deref + ?Sized,
+    H::Target: Contains<N>,
+    N: ?Sized,
+{
+    #[inline]
+    fn outcome(&self) -> ContainsOutcome<'a> {
+        outcome(&**self.0, self.1)
+    }
+}
+
+#[doc(hidden)]
+pub trait DerefContainsDerefNeedle<'a> {
+    fn outcome(&self) -> ContainsOutcome<'a>;
+}
+
+impl<'a, H, N> DerefContainsDerefNeedle<'a> for NeedlePair<'a, H, N>
+where
+    H: Deref + ?Sized,
+    H::Target: Contains<N::Target>,
+    N: Deref + ?Sized,
+{
+    #[inline]
+    fn outcome(&self) -> ContainsOutcome<'a> {
+        outcome(&**self.0, &**self.1)
+    }
+}
+
+#[inline]
+fn outcome<'a, H: Contains<N> + ?Sized, N: ?Sized>(haystack: &'a H, needle: &N) -> ContainsOutcome<'a> {
+    ContainsOutcome { len: Contains::<N>::len(haystack), found: haystack.locate(needle) }
+}
+
+/// How many bytes of the haystack's `Debug` output are shown on failure.
+const PREVIEW_LEN: usize = 64;
+
+/// The start of a haystack's `Debug` output, followed by `...` if there's more,
+/// which is what `chek::contains!` prints instead of the whole haystack.
+#[doc(hidden)]
+pub struct Preview<'a>(pub &'a dyn Debug);
+
+impl Debug for Preview<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = Truncate { f, remaining: PREVIEW_LEN, truncated: false };
+        write!(out, "{:?}", self.0)?;
+        if out.truncated {
+            out.f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+struct Truncate<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    remaining: usize,
+    truncated: bool,
+}
+
+impl Write for Truncate<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.f.write_str(s);
+        }
+        if !self.truncated {
+            let mut end = self.remaining;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            self.f.write_str(&s[..end])?;
+            self.remaining = 0;
+            self.truncated = true;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,535 @@
+
+/// Panics if the collection given by the first expression doesn't contain the
+/// second.
+///
+/// The collection may be a slice, array, `Vec`, `str` or `String` (in which
+/// case the needle may be a substring or a `char`), the keys of a `BTreeMap`
+/// or `HashMap`, a `BTreeSet` or `HashSet`, or anything else implementing
+/// `chek::Contains`. On failure, the message gives the needle, the length of
+/// the collection, and the start of its `Debug` output.
+///
+/// If the collection or the needle doesn't implement `Debug`, it's shown as
+/// `<type name: Debug not available here>` on failure.
+///
+/// A debug-only version is available as `chek::debug_contains!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let primes = [2, 3, 5, 7];
+/// chek::contains!(primes, 5);
+/// chek::contains!(primes[1..], 3, "With a message");
+/// chek::contains!("hello world", "o w");
+/// chek::contains!("hello world", 'w', "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let primes = [2, 3, 5, 7];
+/// // assertion failed: `chek::contains!(left, right)`
+/// //   left: `[2, 3, 5, 7]` = `primes`,
+/// //  right: `4` = `4`
+/// // result: left (length 4) does not contain right
+/// chek::contains!(primes, 4);
+/// ```
+#[macro_export]
+macro_rules! contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            $crate::__contains_assert_fail!("contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome);
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            $crate::__contains_assert_fail!("contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::contains!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// chek::debug_contains!([2, 3, 5, 7], 5);
+/// chek::debug_contains!("hello world", "o w", "With a message");
+/// chek::debug_contains!("hello world", 'w', "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($haystack), &($needle));
+            let outcome = $crate::__contains!(haystack, needle);
+            if !outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome);
+            }
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($haystack), &($needle));
+            let outcome = $crate::__contains!(haystack, needle);
+            if !outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Panics if the collection given by the first expression contains the second.
+///
+/// Accepts the same collections as `chek::contains!`. On failure, the message
+/// gives the needle, the length of the collection, the start of its `Debug`
+/// output, and where the needle was found: its index (for strings, its byte
+/// offset), or the key or element of a map or set that matched it.
+///
+/// If the collection or the needle doesn't implement `Debug`, it's shown as
+/// `<type name: Debug not available here>` on failure.
+///
+/// A debug-only version is available as `chek::debug_not_contains!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let primes = [2, 3, 5, 7];
+/// chek::not_contains!(primes, 4);
+/// chek::not_contains!(primes[1..], 2, "With a message");
+/// chek::not_contains!("hello world", "ow");
+/// chek::not_contains!("hello world", 'x', "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let primes = [2, 3, 5, 7];
+/// // assertion failed: `chek::not_contains!(left, right)`
+/// //   left: `[2, 3, 5, 7]` = `primes`,
+/// //  right: `5` = `5`
+/// // result: left (length 4) contains right at index 2
+/// chek::not_contains!(primes, 5);
+/// ```
+#[macro_export]
+macro_rules! not_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if outcome.is_found() {
+            $crate::__contains_assert_fail!("not_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome);
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if outcome.is_found() {
+            $crate::__contains_assert_fail!("not_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::not_contains!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// chek::debug_not_contains!([2, 3, 5, 7], 4);
+/// chek::debug_not_contains!("hello world", "ow", "With a message");
+/// chek::debug_not_contains!("hello world", 'x', "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_not_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($haystack), &($needle));
+            let outcome = $crate::__contains!(haystack, needle);
+            if outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_not_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome);
+            }
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($haystack), &($needle));
+            let outcome = $crate::__contains!(haystack, needle);
+            if outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_not_contains", haystack, needle, stringify!($haystack), stringify!($needle), outcome, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Panics if the map given by the first expression doesn't have the second as
+/// a key.
+///
+/// This is `chek::contains!`, but only accepts maps (`BTreeMap` and `HashMap`,
+/// or references to them), so that it's clear from the call that the keys are
+/// being checked. As with `contains_key`, the key may be any type the map's
+/// keys can be borrowed as, e.g. a `&str` for a map with `String` keys. On
+/// failure, the message gives the key, the length of the map, and the start of
+/// its `Debug` output.
+///
+/// `BTreeMap` requires the `alloc` feature, and `HashMap` requires the `std`
+/// feature.
+///
+/// A debug-only version is available as `chek::debug_contains_key!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use std::collections::BTreeMap;
+///
+/// let mut ports = BTreeMap::new();
+/// ports.insert(String::from("http"), 80);
+/// ports.insert(String::from("https"), 443);
+/// chek::contains_key!(ports, "http");
+/// chek::contains_key!(&ports, "https", "With a message");
+/// chek::contains_key!(ports, String::from("http"), "With a formatted message: {}", "oh no");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! contains_key {
+    ($map:expr, $key:expr $(,)?) => ({
+        let (haystack, needle) = (&($map), &($key));
+        let haystack = $crate::__internal::as_map(haystack);
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            $crate::__contains_assert_fail!("contains_key", haystack, needle, stringify!($map), stringify!($key), outcome);
+        }
+    });
+    ($map:expr, $key:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($map), &($key));
+        let haystack = $crate::__internal::as_map(haystack);
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            $crate::__contains_assert_fail!("contains_key", haystack, needle, stringify!($map), stringify!($key), outcome, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::contains_key!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use std::collections::BTreeMap;
+///
+/// let ports: BTreeMap<&str, u16> = [("http", 80), ("https", 443)].iter().copied().collect();
+/// // These are no-ops if debug_assertions are off!
+/// chek::debug_contains_key!(ports, "http");
+/// chek::debug_contains_key!(ports, "https", "With a message");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! debug_contains_key {
+    ($map:expr, $key:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($map), &($key));
+            let haystack = $crate::__internal::as_map(haystack);
+            let outcome = $crate::__contains!(haystack, needle);
+            if !outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_contains_key", haystack, needle, stringify!($map), stringify!($key), outcome);
+            }
+        }
+    });
+    ($map:expr, $key:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (haystack, needle) = (&($map), &($key));
+            let haystack = $crate::__internal::as_map(haystack);
+            let outcome = $crate::__contains!(haystack, needle);
+            if !outcome.is_found() {
+                $crate::__contains_assert_fail!("debug_contains_key", haystack, needle, stringify!($map), stringify!($key), outcome, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the collection given by the first
+/// expression doesn't contain the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::contains!`, and accepts the
+/// same collections.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_contains!([2, 3, 5, 7], 5).is_ok());
+/// assert!(chek::try_contains!([2, 3, 5, 7], 4).is_err());
+/// assert!(chek::try_contains!("hello world", "ow", "With a message").is_err());
+/// assert!(chek::try_contains!("hello world", 'x', "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            Err($crate::__internal::contains_failure("contains", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($haystack), stringify!($needle), outcome, None))
+        } else {
+            Ok(())
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            Err($crate::__internal::contains_failure("contains", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($haystack), stringify!($needle), outcome, Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the collection given by the first
+/// expression contains the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::not_contains!`, and accepts the
+/// same collections.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_contains!([2, 3, 5, 7], 4).is_ok());
+/// assert!(chek::try_not_contains!([2, 3, 5, 7], 5).is_err());
+/// assert!(chek::try_not_contains!("hello world", "o w", "With a message").is_err());
+/// assert!(chek::try_not_contains!("hello world", 'w', "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if outcome.is_found() {
+            Err($crate::__internal::contains_failure("not_contains", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($haystack), stringify!($needle), outcome, None))
+        } else {
+            Ok(())
+        }
+    });
+    ($haystack:expr, $needle:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($haystack), &($needle));
+        let outcome = $crate::__contains!(haystack, needle);
+        if outcome.is_found() {
+            Err($crate::__internal::contains_failure("not_contains", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($haystack), stringify!($needle), outcome, Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the map given by the first expression
+/// doesn't have the second as a key, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::contains_key!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use std::collections::BTreeMap;
+///
+/// let ports: BTreeMap<&str, u16> = [("http", 80), ("https", 443)].iter().copied().collect();
+/// assert!(chek::try_contains_key!(ports, "http").is_ok());
+/// assert!(chek::try_contains_key!(ports, "ftp").is_err());
+/// assert!(chek::try_contains_key!(ports, "ftp", "With a message").is_err());
+/// # }
+/// ```
+#[macro_export]
+macro_rules! try_contains_key {
+    ($map:expr, $key:expr $(,)?) => ({
+        let (haystack, needle) = (&($map), &($key));
+        let haystack = $crate::__internal::as_map(haystack);
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            Err($crate::__internal::contains_failure("contains_key", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($map), stringify!($key), outcome, None))
+        } else {
+            Ok(())
+        }
+    });
+    ($map:expr, $key:expr, $($msg_args:tt)+) => ({
+        let (haystack, needle) = (&($map), &($key));
+        let haystack = $crate::__internal::as_map(haystack);
+        let outcome = $crate::__contains!(haystack, needle);
+        if !outcome.is_found() {
+            Err($crate::__internal::contains_failure("contains_key", $crate::__debug!(haystack), $crate::__debug!(needle), stringify!($map), stringify!($key), outcome, Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the collection
+/// given by the first expression doesn't contain the second.
+///
+/// This is the `Result`-returning version of `chek::contains!`, and accepts the
+/// same collections.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_contains!([2, 3, 5, 7], 5);
+///     chek::ensure_contains!([2, 3, 5, 7], 4);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_contains!([2, 3, 5, 7], 4, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_contains!($haystack, $needle) {
+            return Err(From::from(failure));
+        }
+    });
+    ($haystack:expr, $needle:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_contains!($haystack, $needle) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the collection
+/// given by the first expression contains the second.
+///
+/// This is the `Result`-returning version of `chek::not_contains!`, and accepts
+/// the same collections.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_contains!([2, 3, 5, 7], 4);
+///     chek::ensure_not_contains!([2, 3, 5, 7], 5);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_contains!([2, 3, 5, 7], 5, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_contains {
+    ($haystack:expr, $needle:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_contains!($haystack, $needle) {
+            return Err(From::from(failure));
+        }
+    });
+    ($haystack:expr, $needle:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_contains!($haystack, $needle) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the map given by
+/// the first expression doesn't have the second as a key.
+///
+/// This is the `Result`-returning version of `chek::contains_key!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use std::collections::BTreeMap;
+///
+/// fn no_error(ports: &BTreeMap<&str, u16>) -> Result<(), chek::Failure> {
+///     chek::ensure_contains_key!(ports, "http");
+///     chek::ensure_contains_key!(ports, "ftp");
+///     Ok(())
+/// }
+///
+/// fn with_error(ports: &BTreeMap<&str, u16>) -> Result<(), &'static str> {
+///     chek::ensure_contains_key!(ports, "ftp", "With an error");
+///     Ok(())
+/// }
+///
+/// let ports: BTreeMap<&str, u16> = [("http", 80), ("https", 443)].iter().copied().collect();
+/// assert!(no_error(&ports).is_err());
+/// assert_eq!(with_error(&ports), Err("With an error"));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! ensure_contains_key {
+    ($map:expr, $key:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_contains_key!($map, $key) {
+            return Err(From::from(failure));
+        }
+    });
+    ($map:expr, $key:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_contains_key!($map, $key) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
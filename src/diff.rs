@@ -0,0 +1,247 @@
+//! Line-based unified diffs for `chek::equal!` failures, with the `diff`
+//! feature.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Write};
+
+/// How many unchanged lines to show around each change.
+const CONTEXT: usize = 3;
+
+/// Past this many differing lines, we stop looking for a minimal diff, and just
+/// show everything between the common prefix and suffix as changed. Finding
+/// the minimal diff takes memory quadratic in this (about 4 MB at 1000).
+const MAX_EDITS: usize = 1000;
+
+/// How many lines of hunks to show, before the rest are summarized as
+/// `... and N more lines`.
+const MAX_LINES: usize = 200;
+
+/// Writes a unified diff of the `{:#?}` output of `left` and `right`, unless
+/// both fit on a single line, in which case the values shown by the assertion
+/// already say everything.
+pub(crate) fn write_diff(f: &mut fmt::Formatter<'_>, left: &dyn Debug, right: &dyn Debug) -> fmt::Result {
+    let (left, right) = (text(left), text(right));
+    if !left.contains('\n') && !right.contains('\n') {
+        return Ok(());
+    }
+    let (left, right): (Vec<&str>, Vec<&str>) = (left.split('\n').collect(), right.split('\n').collect());
+    let edits = diff_lines(&left, &right);
+    let hunks = hunks(&edits);
+    if hunks.is_empty() {
+        // E.g. values containing NaN, which aren't equal to themselves.
+        return f.write_str("\ndiff (-left, +right): none, both have the same `Debug` output");
+    }
+    f.write_str("\ndiff (-left, +right):")?;
+    let mut shown = 0;
+    for (h, hunk) in hunks.iter().enumerate() {
+        let (mut left_count, mut right_count) = (0, 0);
+        for (edit, _) in &edits[hunk.start..hunk.end] {
+            left_count += (*edit != Edit::Insert) as usize;
+            right_count += (*edit != Edit::Delete) as usize;
+        }
+        write!(f, "\n@@ -{} +{} @@", Span(hunk.left_line, left_count), Span(hunk.right_line, right_count))?;
+        for (i, (edit, line)) in edits[hunk.start..hunk.end].iter().enumerate() {
+            if shown == MAX_LINES {
+                let rest = hunk.end - hunk.start - i + hunks[h + 1..].iter().map(|h| h.end - h.start).sum::<usize>();
+                return write!(f, "\n... and {} more lines", rest);
+            }
+            let marker = match edit {
+                Edit::Same => ' ',
+                Edit::Delete => '-',
+                Edit::Insert => '+',
+            };
+            write!(f, "\n{}{}", marker, line)?;
+            shown += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Formats a value for diffing. Strings are shown as their contents, rather
+/// than as a quoted literal, so that multi-line strings diff line by line.
+fn text(value: &dyn Debug) -> String {
+    let mut text = String::new();
+    // A broken `Debug` impl just gets us a partial diff.
+    let _ = write!(text, "{:#?}", value);
+    unquote(&text).unwrap_or(text)
+}
+
+/// Undoes the escaping done by `<str as Debug>::fmt`, returning `None` if
+/// `text` isn't a string literal.
+fn unquote(text: &str) -> Option<String> {
+    let inner = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return None,
+            '\\' => out.push(match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                c @ ('\\' | '"' | '\'') => c,
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let end = rest.find('}')?;
+                    let c = char::from_u32(u32::from_str_radix(&rest[..end], 16).ok()?)?;
+                    chars = rest[end + 1..].chars();
+                    c
+                }
+                _ => return None,
+            }),
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Edit {
+    Same,
+    Delete,
+    Insert,
+}
+
+/// Finds a minimal line diff with Myers' algorithm, after trimming off any
+/// common prefix and suffix.
+fn diff_lines<'a>(left: &[&'a str], right: &[&'a str]) -> Vec<(Edit, &'a str)> {
+    let prefix = left.iter().zip(right).take_while(|(a, b)| a == b).count();
+    let suffix = left[prefix..].iter().rev().zip(right[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (a, b) = (&left[prefix..left.len() - suffix], &right[prefix..right.len() - suffix]);
+
+    let mut edits: Vec<(Edit, &str)> = left[..prefix].iter().map(|line| (Edit::Same, *line)).collect();
+    match myers(a, b) {
+        Some(middle) => edits.extend(middle),
+        None => {
+            edits.extend(a.iter().map(|line| (Edit::Delete, *line)));
+            edits.extend(b.iter().map(|line| (Edit::Insert, *line)));
+        }
+    }
+    edits.extend(left[left.len() - suffix..].iter().map(|line| (Edit::Same, *line)));
+    edits
+}
+
+/// Returns `None` if the diff needs more than `MAX_EDITS` edits.
+fn myers<'a>(a: &[&'a str], b: &[&'a str]) -> Option<Vec<(Edit, &'a str)>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    // `v[k]` is the furthest `x` reached on diagonal `k = x - y`. It's offset by
+    // one past the largest `d` so that `k - 1` and `k + 1` are always in bounds.
+    let max = (n + m).min(MAX_EDITS as isize);
+    let offset = max + 1;
+    let mut v = alloc::vec![0isize; 2 * offset as usize + 1];
+    // Before round `d`, only diagonals `-d..=d` can have been reached, so that's
+    // all we keep of `v`, at `trace[d * d..][..2 * d + 1]`.
+    let mut trace: Vec<u32> = Vec::new();
+    let mut rounds = 0;
+    let mut found = false;
+    for d in 0..=max {
+        rounds += 1;
+        trace.extend(v[(offset - d) as usize..=(offset + d) as usize].iter().map(|&x| x as u32));
+        for k in (-d..=d).step_by(2) {
+            let i = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[i - 1] < v[i + 1]) { v[i + 1] } else { v[i - 1] + 1 };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[i] = x;
+            if x >= n && y >= m {
+                found = true;
+                break;
+            }
+        }
+        if found {
+            break;
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    // Walk back through each round to recover the path.
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (0..rounds).rev() {
+        let round = &trace[(d * d) as usize..][..(2 * d + 1) as usize];
+        let v = |k: isize| round[(k + d) as usize] as isize;
+        let k = x - y;
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let prev_k = if k == -d || (k != d && v(k - 1) < v(k + 1)) { k + 1 } else { k - 1 };
+            (v(prev_k), v(prev_k) - prev_k)
+        };
+        while x > prev_x && y > prev_y {
+            edits.push((Edit::Same, a[x as usize - 1]));
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                edits.push((Edit::Insert, b[y as usize - 1]));
+            } else {
+                edits.push((Edit::Delete, a[x as usize - 1]));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    edits.reverse();
+    Some(edits)
+}
+
+/// A range of `edits` to show together, and the (1-based) line numbers on each
+/// side where it starts.
+struct Hunk {
+    start: usize,
+    end: usize,
+    left_line: usize,
+    right_line: usize,
+}
+
+/// Groups the changes in `edits` into hunks, each with up to `CONTEXT`
+/// unchanged lines around it. Changes that are close enough for their context
+/// to touch go in the same hunk.
+fn hunks(edits: &[(Edit, &str)]) -> Vec<Hunk> {
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let (mut left_line, mut right_line) = (1, 1);
+    for (i, (edit, _)) in edits.iter().enumerate() {
+        if *edit != Edit::Same {
+            let start = i.saturating_sub(CONTEXT);
+            let end = (i + 1 + CONTEXT).min(edits.len());
+            match hunks.last_mut() {
+                Some(hunk) if hunk.end >= start => hunk.end = end,
+                _ => {
+                    // Line numbers at `start`, counting back from the current line.
+                    let before = &edits[start..i];
+                    hunks.push(Hunk {
+                        start,
+                        end,
+                        left_line: left_line - before.iter().filter(|(e, _)| *e != Edit::Insert).count(),
+                        right_line: right_line - before.iter().filter(|(e, _)| *e != Edit::Delete).count(),
+                    });
+                }
+            }
+        }
+        left_line += (*edit != Edit::Insert) as usize;
+        right_line += (*edit != Edit::Delete) as usize;
+    }
+    hunks
+}
+
+/// The `start,count` part of a hunk header. As in `diff -u`, an empty range
+/// is given by the line before it.
+struct Span(usize, usize);
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Span(start, 1) => write!(f, "{}", start),
+            Span(start, 0) => write!(f, "{},0", start - 1),
+            Span(start, count) => write!(f, "{},{}", start, count),
+        }
+    }
+}
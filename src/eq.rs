@@ -1,7 +1,12 @@
 
 /// Panics if the first expression is not strictly equal than the second.
 ///
-/// Requires that the values implement `PartialEq` and `Debug`.
+/// Requires that the values implement `PartialEq`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`). If there's no `PartialEq` between the values themselves,
+/// they're compared after dereferencing one or both of them, so that e.g.
+/// `Box<T>` can be compared with `T`, and `Box<str>` with `&str`.
 ///
 /// Note: is also aliased as `chek::eq!`. A debug-only version is available as
 /// `chek::debug_equal!`.
@@ -20,21 +25,26 @@
 macro_rules! equal {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left == right) {
-            $crate::__cmp_assert_fail!("equal", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            $crate::__eq_assert_fail!("equal", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left == right) {
-            $crate::__cmp_assert_fail!("equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_eq, left, right) {
+            $crate::__eq_assert_fail!("equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
 
 /// Panics if the first expression is not strictly equal than the second.
 ///
-/// Requires that the values implement `PartialEq` and `Debug`.
+/// Requires that the values implement `PartialEq`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`). If there's no `PartialEq` between the values themselves,
+/// they're compared after dereferencing one or both of them, so that e.g.
+/// `Box<T>` can be compared with `T`, and `Box<str>` with `&str`.
 ///
 /// Note: is also aliased as `chek::equal!`. A debug-only version is available as
 /// `chek::debug_eq!`.
@@ -53,14 +63,14 @@ macro_rules! equal {
 macro_rules! eq {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left == right) {
-            $crate::__cmp_assert_fail!("eq", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            $crate::__eq_assert_fail!("eq", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left == right) {
-            $crate::__cmp_assert_fail!("eq", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_eq, left, right) {
+            $crate::__eq_assert_fail!("eq", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
@@ -85,16 +95,16 @@ macro_rules! debug_equal {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left == right) {
-                $crate::__cmp_assert_fail!("debug_equal", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_eq, left, right) {
+                $crate::__eq_assert_fail!("debug_equal", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left == right) {
-                $crate::__cmp_assert_fail!("debug_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_eq, left, right) {
+                $crate::__eq_assert_fail!("debug_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
@@ -120,18 +130,184 @@ macro_rules! debug_eq {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left == right) {
-                $crate::__cmp_assert_fail!("debug_eq", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_eq, left, right) {
+                $crate::__eq_assert_fail!("debug_eq", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left == right) {
-                $crate::__cmp_assert_fail!("debug_eq", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_eq, left, right) {
+                $crate::__eq_assert_fail!("debug_eq", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
 }
 
+
+/// Returns `Err(chek::Failure)` if the first expression is not equal to the
+/// second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::equal!`. Requires that the
+/// values implement `PartialEq`.
+///
+/// Note: is also aliased as `chek::try_eq!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_equal!(4, 4).is_ok());
+/// assert!(chek::try_equal!(4, 3).is_err());
+/// assert!(chek::try_equal!(4, 3, "With a message").is_err());
+/// assert!(chek::try_equal!(4, 3, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            Err($crate::__internal::eq_failure("equal", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            Err($crate::__internal::eq_failure("equal", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns `Err(chek::Failure)` if the first expression is not equal to the
+/// second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::eq!`. Requires that the
+/// values implement `PartialEq`.
+///
+/// Note: is also aliased as `chek::try_equal!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_eq!(4, 4).is_ok());
+/// assert!(chek::try_eq!(4, 3).is_err());
+/// assert!(chek::try_eq!(4, 3, "With a message").is_err());
+/// assert!(chek::try_eq!(4, 3, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            Err($crate::__internal::eq_failure("eq", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_eq, left, right) {
+            Err($crate::__internal::eq_failure("eq", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not equal to the second.
+///
+/// This is the `Result`-returning version of `chek::equal!`. Requires that the
+/// values implement `PartialEq`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_equal!(4, 4);
+///     chek::ensure_equal!(4, 3);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_equal!(4, 3, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_equal!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_equal!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not equal to the second.
+///
+/// This is the `Result`-returning version of `chek::eq!`. Requires that the
+/// values implement `PartialEq`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_eq!(4, 4);
+///     chek::ensure_eq!(4, 3);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_eq!(4, 3, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_eq!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_eq!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,642 @@
+
+/// Panics unless the two floats have exactly the same bit pattern, i.e. unless
+/// `left.to_bits() == right.to_bits()`.
+///
+/// Unlike `chek::eq!`, this fails for `0.0` and `-0.0`, and passes for two NaNs
+/// with the same payload, which is what determinism and serialization tests
+/// usually want.
+///
+/// On failure, the message shows both bit patterns in hex, and the sign,
+/// exponent and mantissa they decode to.
+///
+/// A debug-only version is available as `chek::debug_bits_eq!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::bits_eq!(1.5f32, 1.5);
+/// chek::bits_eq!(f64::NAN, f64::NAN, "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = (0.0f32, -0.0f32);
+/// // assertion failed: `chek::bits_eq!(left, right)`
+/// //   left: `0.0` = `x`,
+/// //  right: `-0.0` = `y`
+/// //   bits: `0x00000000` vs `0x80000000`
+/// // fields: sign `0` vs `1`, exponent `0x00` vs `0x00`, mantissa `0x000000` vs `0x000000`
+/// chek::bits_eq!(x, y);
+/// ```
+#[macro_export]
+macro_rules! bits_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::bits_eq(left, right) {
+            $crate::__details_assert_fail!("bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right));
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::bits_eq(left, right) {
+            $crate::__details_assert_fail!("bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right),
+                                         format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::bits_eq!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_bits_eq!(1.5f32, 1.5);
+/// chek::debug_bits_eq!(f64::NAN, f64::NAN, "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_bits_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::bits_eq(left, right) {
+                $crate::__details_assert_fail!("debug_bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::bits_eq(left, right) {
+                $crate::__details_assert_fail!("debug_bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right),
+                                             format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two floats have different bit patterns, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::bits_eq!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_bits_eq!(1.5f32, 1.5).is_ok());
+/// assert!(chek::try_bits_eq!(0.0, -0.0, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_bits_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::bits_eq(left, right) {
+            Err($crate::__internal::details_failure("bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::bits_eq(left, right) {
+            Err($crate::__internal::details_failure("bits_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::BitsDiff(left, right),
+                                                  Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two floats have
+/// different bit patterns.
+///
+/// This is the `Result`-returning version of `chek::bits_eq!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_bits_eq!(1.5f32, 1.5);
+///     chek::ensure_bits_eq!(0.0, -0.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_bits_eq!(0.0, -0.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_bits_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_bits_eq!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_bits_eq!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the first float is less than the second in the IEEE 754 total
+/// order, as determined by `f64::total_cmp` (or `f32::total_cmp`).
+///
+/// In the total order, `-0.0` is less than `0.0`, and NaNs are ordered too:
+/// positive NaNs are greater than `+inf`, and negative NaNs are less than
+/// `-inf`.
+///
+/// On failure, the message shows the bit patterns of both floats in hex, since
+/// `Debug` doesn't tell NaNs with different signs apart.
+///
+/// A debug-only version is available as `chek::debug_total_lt!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::total_lt!(-0.0f32, 0.0);
+/// chek::total_lt!(f64::INFINITY, f64::NAN, "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = (0.0f64, -0.0f64);
+/// // assertion failed: `chek::total_lt!(left, right)`
+/// //   left: `0.0` = `x`,
+/// //  right: `-0.0` = `y`
+/// //   bits: `0x0000000000000000` vs `0x8000000000000000`
+/// // result: left was greater than right in the total order
+/// chek::total_lt!(x, y);
+/// ```
+#[macro_export]
+macro_rules! total_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_lt() {
+            $crate::__details_assert_fail!("total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_lt() {
+            $crate::__details_assert_fail!("total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                         format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::total_lt!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_total_lt!(-0.0f32, 0.0);
+/// chek::debug_total_lt!(f64::INFINITY, f64::NAN, "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_total_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_lt() {
+                $crate::__details_assert_fail!("debug_total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_lt() {
+                $crate::__details_assert_fail!("debug_total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                             format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the first float is not less than the second in the
+/// total order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::total_lt!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_total_lt!(-0.0f32, 0.0).is_ok());
+/// assert!(chek::try_total_lt!(f64::NAN, f64::INFINITY, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_total_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_lt() {
+            Err($crate::__internal::details_failure("total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_lt() {
+            Err($crate::__internal::details_failure("total_lt", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                                  Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first float is
+/// not less than the second in the total order.
+///
+/// This is the `Result`-returning version of `chek::total_lt!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_total_lt!(-0.0f32, 0.0);
+///     chek::ensure_total_lt!(f64::NAN, f64::INFINITY);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_total_lt!(f64::NAN, f64::INFINITY, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_total_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_lt!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_lt!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the first float is less than or equal to the second in the
+/// IEEE 754 total order, as determined by `f64::total_cmp` (or
+/// `f32::total_cmp`). See `chek::total_lt!` for how the total order works.
+///
+/// A debug-only version is available as `chek::debug_total_le!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::total_le!(-0.0f32, 0.0);
+/// chek::total_le!(f64::NAN, f64::NAN, "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = (f64::NAN, f64::INFINITY);
+/// // assertion failed: `chek::total_le!(left, right)`
+/// //   left: `NaN` = `x`,
+/// //  right: `inf` = `y`
+/// //   bits: `0x7ff8000000000000` vs `0x7ff0000000000000`
+/// // result: left was greater than right in the total order
+/// chek::total_le!(x, y);
+/// ```
+#[macro_export]
+macro_rules! total_le {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_le() {
+            $crate::__details_assert_fail!("total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_le() {
+            $crate::__details_assert_fail!("total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                         format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::total_le!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_total_le!(-0.0f32, 0.0);
+/// chek::debug_total_le!(f64::NAN, f64::NAN, "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_total_le {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_le() {
+                $crate::__details_assert_fail!("debug_total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_le() {
+                $crate::__details_assert_fail!("debug_total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                             format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the first float is greater than the second in the
+/// total order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::total_le!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_total_le!(-0.0f32, 0.0).is_ok());
+/// assert!(chek::try_total_le!(f64::NAN, f64::INFINITY, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_total_le {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_le() {
+            Err($crate::__internal::details_failure("total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_le() {
+            Err($crate::__internal::details_failure("total_le", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                                  Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first float is
+/// greater than the second in the total order.
+///
+/// This is the `Result`-returning version of `chek::total_le!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_total_le!(-0.0f32, 0.0);
+///     chek::ensure_total_le!(f64::NAN, f64::INFINITY);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_total_le!(f64::NAN, f64::INFINITY, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_total_le {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_le!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_le!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the two floats are equal in the IEEE 754 total order, as
+/// determined by `f64::total_cmp` (or `f32::total_cmp`). See `chek::total_lt!`
+/// for how the total order works.
+///
+/// Floats are only equal in the total order if they have the same bit pattern,
+/// so this is the same check as `chek::bits_eq!`, with a different failure
+/// message.
+///
+/// A debug-only version is available as `chek::debug_total_eq!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::total_eq!(1.5f32, 1.5);
+/// chek::total_eq!(f64::NAN, f64::NAN, "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = (f32::NAN, -f32::NAN);
+/// // assertion failed: `chek::total_eq!(left, right)`
+/// //   left: `NaN` = `x`,
+/// //  right: `NaN` = `y`
+/// //   bits: `0x7fc00000` vs `0xffc00000`
+/// // result: left was greater than right in the total order
+/// chek::total_eq!(x, y);
+/// ```
+#[macro_export]
+macro_rules! total_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_eq() {
+            $crate::__details_assert_fail!("total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_eq() {
+            $crate::__details_assert_fail!("total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                         format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::total_eq!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_total_eq!(1.5f32, 1.5);
+/// chek::debug_total_eq!(f64::NAN, f64::NAN, "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_total_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_eq() {
+                $crate::__details_assert_fail!("debug_total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = ($left, $right);
+            if !$crate::__internal::total_cmp(left, right).is_eq() {
+                $crate::__details_assert_fail!("debug_total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                             format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two floats are not equal in the total order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::total_eq!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_total_eq!(1.5f32, 1.5).is_ok());
+/// assert!(chek::try_total_eq!(0.0, -0.0, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_total_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_eq() {
+            Err($crate::__internal::details_failure("total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = ($left, $right);
+        if !$crate::__internal::total_cmp(left, right).is_eq() {
+            Err($crate::__internal::details_failure("total_eq", &left, &right, stringify!($left), stringify!($right), &$crate::__internal::TotalOrder(left, right),
+                                                  Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two floats are
+/// not equal in the total order.
+///
+/// This is the `Result`-returning version of `chek::total_eq!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_total_eq!(1.5f32, 1.5);
+///     chek::ensure_total_eq!(0.0, -0.0);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_total_eq!(0.0, -0.0, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_total_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_eq!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_total_eq!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,265 @@
+use core::fmt;
+use core::panic::Location;
+
+/// A description of a failed check, produced by the `chek::try_*!` and
+/// `chek::ensure_*!` macros, and passed to the handler installed by
+/// `chek::set_failure_handler`.
+///
+/// This holds everything the panicking version of the macro would have printed:
+/// the name of the check (e.g. `"lt"` or `"almost_equal"`), the source text and
+/// `Debug` output of the values involved, the optional message, and the
+/// location of the check in the source.
+///
+/// Its `Display` implementation produces (nearly) the same message the
+/// panicking macro would have. With the `std` feature, it also implements
+/// `std::error::Error`, so the `ensure_` macros work in functions returning
+/// `Result<_, Box<dyn std::error::Error>>`.
+///
+/// # Truncation
+///
+/// Without the `alloc` feature, the formatted values and message are stored
+/// inline, and are truncated to 128 bytes each. With the `alloc` feature,
+/// they're stored in `String`s and are never truncated.
+///
+/// # Example
+///
+/// ```rust
+/// let failure = chek::try_lt!(5, 3, "five isn't less than three").unwrap_err();
+/// assert_eq!(failure.op(), "lt");
+/// assert_eq!(failure.left_expr(), "5");
+/// assert_eq!(failure.right_value(), Some("3"));
+/// assert_eq!(failure.message(), Some("five isn't less than three"));
+/// println!("{} (at {})", failure, failure.location());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Failure {
+    op: &'static str,
+    left_expr: &'static str,
+    right_expr: Option<&'static str>,
+    left_value: Text,
+    right_value: Option<Text>,
+    message: Option<Text>,
+    details: Option<Text>,
+    location: &'static Location<'static>,
+    shape: Shape,
+}
+
+/// How the header of a `Failure` is laid out, to match the panic message.
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    /// `chek::lt!(left, right)`, followed by each value and its source text.
+    Values,
+    /// `chek::check!(expr)`, followed by just the details.
+    Expr,
+    /// `chek::matches!(value, pattern)`, followed by the value.
+    Pattern,
+}
+
+impl Failure {
+    /// The name of the check that failed, e.g. `"lt"` for `chek::try_lt!`.
+    #[inline]
+    pub fn op(&self) -> &'static str {
+        self.op
+    }
+
+    /// The source text of the first (or only) argument to the check.
+    #[inline]
+    pub fn left_expr(&self) -> &'static str {
+        self.left_expr
+    }
+
+    /// The source text of the second argument to the check, or `None` for
+    /// checks that only take one value, such as `chek::try_almost_zero!`.
+    #[inline]
+    pub fn right_expr(&self) -> Option<&'static str> {
+        self.right_expr
+    }
+
+    /// The `Debug` output of the first (or only) argument to the check.
+    #[inline]
+    pub fn left_value(&self) -> &str {
+        self.left_value.as_str()
+    }
+
+    /// The `Debug` output of the second argument to the check, or `None` for
+    /// checks that only take one value, or whose second argument is a pattern,
+    /// such as `chek::try_matches!`.
+    #[inline]
+    pub fn right_value(&self) -> Option<&str> {
+        self.right_value.as_ref().map(Text::as_str)
+    }
+
+    /// The formatted message passed to the check, if any.
+    #[inline]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_ref().map(Text::as_str)
+    }
+
+    /// Any additional information the check reported about the failure, such as
+    /// the value of each subexpression for `chek::check!`, or how the values
+    /// actually compared for `chek::try_lt!` and the other ordering checks.
+    #[inline]
+    pub fn details(&self) -> Option<&str> {
+        self.details.as_ref().map(Text::as_str)
+    }
+
+    /// The location of the check in the source code.
+    #[inline]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "assertion failed: `chek::{}!(", self.op)?;
+        match (self.shape, self.right_expr) {
+            (Shape::Values, Some(_)) => f.write_str("left, right"),
+            (Shape::Values, None) => f.write_str("value"),
+            (Shape::Expr, _) => f.write_str(self.left_expr),
+            (Shape::Pattern, pattern) => write!(f, "{}, {}", self.left_expr, pattern.unwrap_or("_")),
+        }?;
+        f.write_str(")`")?;
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message.as_str())?;
+        }
+        match (self.shape, &self.right_value, self.right_expr) {
+            (Shape::Expr, _, _) => Ok(()),
+            (Shape::Pattern, _, _) => write!(f, "\n value: `{}`", self.left_value.as_str()),
+            (Shape::Values, Some(right_value), Some(right_expr)) => write!(
+                f,
+                "\n  left: `{}` = `{}`,\n right: `{}` = `{}`",
+                self.left_value.as_str(),
+                self.left_expr,
+                right_value.as_str(),
+                right_expr,
+            ),
+            (Shape::Values, _, _) => write!(f, "\n value: `{}` = `{}`", self.left_value.as_str(), self.left_expr),
+        }?;
+        if let Some(details) = &self.details {
+            f.write_str(details.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Failure {}
+
+impl Failure {
+    #[track_caller]
+    pub(crate) fn new(
+        op: &'static str,
+        left: (&dyn fmt::Debug, &'static str),
+        right: Option<(&dyn fmt::Debug, &'static str)>,
+        msg: Option<fmt::Arguments<'_>>,
+        details: Option<&dyn fmt::Display>,
+    ) -> Self {
+        Failure {
+            op,
+            left_expr: left.1,
+            right_expr: right.map(|r| r.1),
+            left_value: Text::debug(left.0),
+            right_value: right.map(|r| Text::debug(r.0)),
+            message: msg.map(Text::display),
+            details: details.map(Text::display).filter(|details| !details.as_str().is_empty()),
+            location: Location::caller(),
+            shape: Shape::Values,
+        }
+    }
+
+    /// A failure of a check on a whole expression, like `chek::check!`, whose
+    /// header shows the expression, rather than a value.
+    #[track_caller]
+    pub(crate) fn expr(op: &'static str, expr_str: &'static str, msg: Option<fmt::Arguments<'_>>, details: &dyn fmt::Display) -> Self {
+        Failure { shape: Shape::Expr, ..Failure::new(op, (&false, expr_str), None, msg, Some(details)) }
+    }
+
+    /// A failure of a check that matches a value against a pattern, like
+    /// `chek::matches!`. The source of the pattern is kept as `right_expr`.
+    #[track_caller]
+    pub(crate) fn pattern(
+        op: &'static str,
+        value: (&dyn fmt::Debug, &'static str),
+        pat_str: &'static str,
+        msg: Option<fmt::Arguments<'_>>,
+    ) -> Self {
+        Failure { shape: Shape::Pattern, right_expr: Some(pat_str), ..Failure::new(op, value, None, msg, None) }
+    }
+}
+
+/// Formatted text stored in a `Failure`.
+#[cfg(feature = "alloc")]
+#[derive(Clone)]
+struct Text(alloc::string::String);
+
+#[cfg(feature = "alloc")]
+impl Text {
+    fn display(v: impl fmt::Display) -> Self {
+        use alloc::string::ToString;
+        Text(v.to_string())
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Formatted text stored in a `Failure`, truncated to fit in a fixed-size
+/// buffer.
+#[cfg(not(feature = "alloc"))]
+#[derive(Clone)]
+struct Text {
+    buf: [u8; Text::CAPACITY],
+    len: usize,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl Text {
+    const CAPACITY: usize = 128;
+
+    fn display(v: impl fmt::Display) -> Self {
+        use fmt::Write;
+        let mut text = Text { buf: [0; Text::CAPACITY], len: 0 };
+        // `write_str` never fails (it truncates instead), so an error here is
+        // from a broken `Display` impl, and we keep whatever we got.
+        let _ = write!(text, "{}", v);
+        text
+    }
+
+    fn as_str(&self) -> &str {
+        // We only ever copy in whole `char`s, so this can't fail.
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or("")
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl fmt::Write for Text {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let mut end = s.len().min(Text::CAPACITY - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        Ok(())
+    }
+}
+
+impl Text {
+    fn debug(v: &dyn fmt::Debug) -> Self {
+        struct AsDisplay<'a>(&'a dyn fmt::Debug);
+        impl fmt::Display for AsDisplay<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(self.0, f)
+            }
+        }
+        Text::display(AsDisplay(v))
+    }
+}
+
+impl fmt::Debug for Text {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
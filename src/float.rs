@@ -0,0 +1,378 @@
+//! Floating point helpers for the tolerance assertions, e.g.
+//! `chek::abs_diff_le!`, the classification assertions, e.g. `chek::finite!`,
+//! and the exact comparisons, e.g. `chek::bits_eq!`. These only use `core`, so
+//! they don't need `std`'s float methods.
+
+use core::cmp::Ordering;
+use core::fmt::{self, Debug};
+use core::num::FpCategory;
+
+use crate::__internal::{slice_excerpt, SliceMismatch};
+
+/// Implemented for `f32` and `f64`.
+#[doc(hidden)]
+pub trait Float: Copy + PartialOrd + Debug {
+    /// How many bits of the representation are the exponent.
+    const EXPONENT_BITS: u32;
+    /// How many bits of the representation are the mantissa, not counting the
+    /// implicit leading bit.
+    const MANTISSA_BITS: u32;
+
+    fn abs(self) -> Self;
+    fn abs_diff(self, other: Self) -> Self;
+    /// `|a - b| / max(|a|, |b|)`, or zero if both are zero.
+    fn rel_diff(self, other: Self) -> Self;
+    /// `limit * max(|a|, |b|)`.
+    fn scaled(limit: Self, a: Self, b: Self) -> Self;
+    /// How many representable values apart the two are, counting `-0.0` and
+    /// `0.0` as the same value. `None` if either is NaN.
+    fn ulps(self, other: Self) -> Option<u64>;
+    /// Whether this is in `class`.
+    fn is(self, class: FloatClass) -> bool;
+    /// What kind of value this is, e.g. `"subnormal"` or `"-inf"`.
+    fn describe(self) -> &'static str;
+    /// The bit pattern, zero-extended.
+    fn bits(self) -> u64;
+    /// Compares using the IEEE 754 `totalOrder` predicate, like `f64::total_cmp`.
+    fn total_order(self, other: Self) -> Ordering;
+}
+
+macro_rules! impl_float {
+    ($f:ident, $bits:ident) => {
+        impl Float for $f {
+            const EXPONENT_BITS: u32 = $bits::BITS - $f::MANTISSA_DIGITS;
+            const MANTISSA_BITS: u32 = $f::MANTISSA_DIGITS - 1;
+
+            #[inline]
+            fn abs(self) -> Self {
+                $f::from_bits(self.to_bits() & !(1 << ($bits::BITS - 1)))
+            }
+
+            #[inline]
+            fn abs_diff(self, other: Self) -> Self {
+                (self - other).abs()
+            }
+
+            #[inline]
+            fn rel_diff(self, other: Self) -> Self {
+                let scale = Float::scaled(1.0, self, other);
+                if scale == 0.0 {
+                    0.0
+                } else {
+                    self.abs_diff(other) / scale
+                }
+            }
+
+            #[inline]
+            fn scaled(limit: Self, a: Self, b: Self) -> Self {
+                let (a, b) = (a.abs(), b.abs());
+                limit * if a > b { a } else { b }
+            }
+
+            #[inline]
+            fn ulps(self, other: Self) -> Option<u64> {
+                if self.is_nan() || other.is_nan() {
+                    return None;
+                }
+                // Sign-magnitude to two's complement, so that adjacent floats
+                // are adjacent integers.
+                let key = |f: $f| {
+                    let magnitude = i128::from(f.abs().to_bits());
+                    if f.is_sign_negative() {
+                        -magnitude
+                    } else {
+                        magnitude
+                    }
+                };
+                Some((key(self) - key(other)).unsigned_abs() as u64)
+            }
+
+            #[inline]
+            fn is(self, class: FloatClass) -> bool {
+                match class {
+                    FloatClass::Finite => self.is_finite(),
+                    FloatClass::NotNan => !self.is_nan(),
+                    FloatClass::Normal => self.is_normal(),
+                    FloatClass::SignPositive => self.is_sign_positive(),
+                    FloatClass::SignNegative => self.is_sign_negative(),
+                }
+            }
+
+            fn describe(self) -> &'static str {
+                let negative = self.is_sign_negative();
+                match self.classify() {
+                    FpCategory::Nan if negative => "-NaN",
+                    FpCategory::Nan => "NaN",
+                    FpCategory::Infinite if negative => "-inf",
+                    FpCategory::Infinite => "+inf",
+                    FpCategory::Zero if negative => "negative zero",
+                    FpCategory::Zero => "zero",
+                    FpCategory::Subnormal if negative => "negative subnormal",
+                    FpCategory::Subnormal => "subnormal",
+                    FpCategory::Normal if negative => "negative normal",
+                    FpCategory::Normal => "normal",
+                }
+            }
+
+            #[inline]
+            fn bits(self) -> u64 {
+                self.to_bits().into()
+            }
+
+            #[inline]
+            fn total_order(self, other: Self) -> Ordering {
+                self.total_cmp(&other)
+            }
+        }
+    };
+}
+
+impl_float!(f32, u32);
+impl_float!(f64, u64);
+
+/// Used by `chek::abs_diff_le!`.
+#[doc(hidden)]
+#[inline]
+pub fn abs_diff_le<F: Float>(left: F, right: F, max: F) -> bool {
+    left == right || left.abs_diff(right) <= max
+}
+
+/// Used by `chek::rel_diff_le!`.
+#[doc(hidden)]
+#[inline]
+pub fn rel_diff_le<F: Float>(left: F, right: F, max: F) -> bool {
+    left == right || left.abs_diff(right) <= F::scaled(max, left, right)
+}
+
+/// Used by `chek::ulps_le!`.
+#[doc(hidden)]
+#[inline]
+pub fn ulps_le<F: Float>(left: F, right: F, max: u64) -> bool {
+    left == right || left.ulps(right).is_some_and(|ulps| ulps <= max)
+}
+
+/// How far apart two floats are, by each measure, and the limit that a
+/// tolerance assertion allowed (described by `kind`, e.g. `"absolute"`).
+#[doc(hidden)]
+pub struct FloatDiff<F, L> {
+    abs: F,
+    rel: F,
+    ulps: Option<u64>,
+    kind: &'static str,
+    limit: L,
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn float_diff<F: Float, L: Debug>(left: F, right: F, kind: &'static str, limit: L) -> FloatDiff<F, L> {
+    FloatDiff { abs: left.abs_diff(right), rel: left.rel_diff(right), ulps: left.ulps(right), kind, limit }
+}
+
+impl<F: Debug, L: Debug> fmt::Display for FloatDiff<F, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n  diff: `{:?}` (absolute), `{:?}` (relative)", self.abs, self.rel)?;
+        if let Some(ulps) = self.ulps {
+            write!(f, ", `{}` (ulps)", ulps)?;
+        }
+        write!(f, "\n limit: `{:?}` ({})", self.limit, self.kind)
+    }
+}
+
+/// Used by `chek::bits_eq!`.
+#[doc(hidden)]
+#[inline]
+pub fn bits_eq<F: Float>(left: F, right: F) -> bool {
+    left.bits() == right.bits()
+}
+
+/// Used by `chek::total_lt!` and friends.
+#[doc(hidden)]
+#[inline]
+pub fn total_cmp<F: Float>(left: F, right: F) -> Ordering {
+    left.total_order(right)
+}
+
+/// Formats a float's bit pattern, or one of the fields it's made of, as hex
+/// padded to the field's width.
+struct Hex(u64, u32);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.1.div_ceil(4) as usize;
+        write!(f, "`{:#0w$x}`", self.0, w = digits + 2)
+    }
+}
+
+fn hex_bits<F: Float>(value: F) -> Hex {
+    Hex(value.bits(), 8 * core::mem::size_of::<F>() as u32)
+}
+
+/// The bit patterns of two floats compared by `chek::bits_eq!`, and the sign,
+/// exponent and mantissa they decode to.
+#[doc(hidden)]
+pub struct BitsDiff<F>(pub F, pub F);
+
+impl<F: Float> fmt::Display for BitsDiff<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = |value: F| {
+            let bits = value.bits();
+            let sign = bits >> (F::EXPONENT_BITS + F::MANTISSA_BITS);
+            let exponent = (bits >> F::MANTISSA_BITS) & ((1 << F::EXPONENT_BITS) - 1);
+            let mantissa = bits & ((1 << F::MANTISSA_BITS) - 1);
+            (sign, Hex(exponent, F::EXPONENT_BITS), Hex(mantissa, F::MANTISSA_BITS))
+        };
+        let (left, right) = (fields(self.0), fields(self.1));
+        write!(f, "\n  bits: {} vs {}", hex_bits(self.0), hex_bits(self.1))?;
+        write!(f, "\nfields: sign `{}` vs `{}`, exponent {} vs {}, mantissa {} vs {}", left.0, right.0, left.1, right.1, left.2, right.2)
+    }
+}
+
+/// The bit patterns of two floats compared by `chek::total_lt!` and friends,
+/// and how they compared. The bits tell apart values that `Debug` doesn't,
+/// like NaNs with different signs.
+#[doc(hidden)]
+pub struct TotalOrder<F>(pub F, pub F);
+
+impl<F: Float> fmt::Display for TotalOrder<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\n  bits: {} vs {}", hex_bits(self.0), hex_bits(self.1))?;
+        let ord = match self.0.total_order(self.1) {
+            Ordering::Less => "less than",
+            Ordering::Equal => "equal to",
+            Ordering::Greater => "greater than",
+        };
+        write!(f, "\nresult: left was {} right in the total order", ord)
+    }
+}
+
+/// What the float classification assertions check for, e.g. `Finite` for
+/// `chek::finite!`.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub enum FloatClass {
+    Finite,
+    NotNan,
+    Normal,
+    SignPositive,
+    SignNegative,
+}
+
+/// Implemented for `f32`, `f64`, and slices and arrays of them (and `Vec`s,
+/// with the `alloc` feature), which are what the float classification
+/// assertions accept.
+#[doc(hidden)]
+pub trait Floats {
+    type Float: Float;
+    /// The floats to check, and whether they're the elements of a slice
+    /// (rather than a single value).
+    fn floats(&self) -> (&[Self::Float], bool);
+}
+
+impl Floats for f32 {
+    type Float = f32;
+    #[inline]
+    fn floats(&self) -> (&[f32], bool) {
+        (core::slice::from_ref(self), false)
+    }
+}
+
+impl Floats for f64 {
+    type Float = f64;
+    #[inline]
+    fn floats(&self) -> (&[f64], bool) {
+        (core::slice::from_ref(self), false)
+    }
+}
+
+impl<F: Float> Floats for [F] {
+    type Float = F;
+    #[inline]
+    fn floats(&self) -> (&[F], bool) {
+        (self, true)
+    }
+}
+
+impl<F: Float, const N: usize> Floats for [F; N] {
+    type Float = F;
+    #[inline]
+    fn floats(&self) -> (&[F], bool) {
+        (self, true)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Float> Floats for alloc::vec::Vec<F> {
+    type Float = F;
+    #[inline]
+    fn floats(&self) -> (&[F], bool) {
+        (self, true)
+    }
+}
+
+impl<T: Floats + ?Sized> Floats for &T {
+    type Float = T::Float;
+    #[inline]
+    fn floats(&self) -> (&[T::Float], bool) {
+        (**self).floats()
+    }
+}
+
+impl<T: Floats + ?Sized> Floats for &mut T {
+    type Float = T::Float;
+    #[inline]
+    fn floats(&self) -> (&[T::Float], bool) {
+        (**self).floats()
+    }
+}
+
+/// The first float that wasn't in the class a classification assertion
+/// checked for.
+#[doc(hidden)]
+pub struct ClassMismatch<'a, F> {
+    floats: &'a [F],
+    // `None` if the value wasn't a slice.
+    index: Option<usize>,
+    float: F,
+}
+
+/// Used by `chek::finite!` and friends. Returns the first of the floats in
+/// `value` that isn't in `class`, if any.
+#[doc(hidden)]
+#[inline]
+pub fn class_mismatch<T: Floats + ?Sized>(value: &T, class: FloatClass) -> Option<ClassMismatch<'_, T::Float>> {
+    let (floats, is_slice) = value.floats();
+    let index = floats.iter().position(|f| !f.is(class))?;
+    Some(ClassMismatch { floats, index: if is_slice { Some(index) } else { None }, float: floats[index] })
+}
+
+impl<'a, F: Float> ClassMismatch<'a, F> {
+    /// What to print as the value: either the float itself, or the elements
+    /// of the slice around it.
+    #[inline]
+    pub fn value(&self) -> ClassValue<'_, 'a, F> {
+        ClassValue(self)
+    }
+}
+
+#[doc(hidden)]
+pub struct ClassValue<'b, 'a, F>(&'b ClassMismatch<'a, F>);
+
+impl<F: Float> Debug for ClassValue<'_, '_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ClassMismatch { floats, index, float } = self.0;
+        match *index {
+            Some(index) => slice_excerpt(*floats, SliceMismatch::element(index, floats.len())).fmt(f),
+            None => float.fmt(f),
+        }
+    }
+}
+
+impl<F: Float> fmt::Display for ClassMismatch<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\nresult: ")?;
+        if let Some(index) = self.index {
+            write!(f, "element {} ", index)?;
+        }
+        write!(f, "was {} (bits: {})", self.float.describe(), hex_bits(self.float))
+    }
+}
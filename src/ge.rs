@@ -1,7 +1,10 @@
 
 /// Panics if the first expression is not strictly greater_or_equal than the second.
 ///
-/// Requires that the values implement `PartialOrd` and `Debug`.
+/// Requires that the values implement `PartialOrd`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`).
 ///
 /// Note: is also aliased as `chek::ge!`. A debug-only version is available as
 /// `chek::debug_greater_or_equal!`.
@@ -24,21 +27,24 @@
 macro_rules! greater_or_equal {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left >= right) {
-            $crate::__cmp_assert_fail!("greater_or_equal", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            $crate::__ord_assert_fail!("greater_or_equal", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left >= right) {
-            $crate::__cmp_assert_fail!("greater_or_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_ge, left, right) {
+            $crate::__ord_assert_fail!("greater_or_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
 
 /// Panics if the first expression is not strictly greater_or_equal than the second.
 ///
-/// Requires that the values implement `PartialOrd` and `Debug`.
+/// Requires that the values implement `PartialOrd`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`).
 ///
 /// Note: is also aliased as `chek::greater_or_equal!`. A debug-only version is available as
 /// `chek::debug_ge!`.
@@ -58,14 +64,14 @@ macro_rules! greater_or_equal {
 macro_rules! ge {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left >= right) {
-            $crate::__cmp_assert_fail!("ge", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            $crate::__ord_assert_fail!("ge", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left >= right) {
-            $crate::__cmp_assert_fail!("ge", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_ge, left, right) {
+            $crate::__ord_assert_fail!("ge", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
@@ -91,16 +97,16 @@ macro_rules! debug_greater_or_equal {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left >= right) {
-                $crate::__cmp_assert_fail!("debug_greater_or_equal", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_ge, left, right) {
+                $crate::__ord_assert_fail!("debug_greater_or_equal", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left >= right) {
-                $crate::__cmp_assert_fail!("debug_greater_or_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_ge, left, right) {
+                $crate::__ord_assert_fail!("debug_greater_or_equal", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
@@ -127,18 +133,187 @@ macro_rules! debug_ge {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left >= right) {
-                $crate::__cmp_assert_fail!("debug_ge", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_ge, left, right) {
+                $crate::__ord_assert_fail!("debug_ge", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left >= right) {
-                $crate::__cmp_assert_fail!("debug_ge", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_ge, left, right) {
+                $crate::__ord_assert_fail!("debug_ge", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
 }
 
+
+/// Returns `Err(chek::Failure)` if the first expression is not greater than or
+/// equal to the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::greater_or_equal!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// Note: is also aliased as `chek::try_ge!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_greater_or_equal!(4, 4).is_ok());
+/// assert!(chek::try_greater_or_equal!(3, 4).is_err());
+/// assert!(chek::try_greater_or_equal!(3, 4, "With a message").is_err());
+/// assert!(chek::try_greater_or_equal!(3, 4, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_greater_or_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            Err($crate::__internal::ord_failure("greater_or_equal", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            Err($crate::__internal::ord_failure("greater_or_equal", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns `Err(chek::Failure)` if the first expression is not greater than or
+/// equal to the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::ge!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// Note: is also aliased as `chek::try_greater_or_equal!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_ge!(4, 4).is_ok());
+/// assert!(chek::try_ge!(3, 4).is_err());
+/// assert!(chek::try_ge!(3, 4, "With a message").is_err());
+/// assert!(chek::try_ge!(3, 4, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_ge {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            Err($crate::__internal::ord_failure("ge", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_ge, left, right) {
+            Err($crate::__internal::ord_failure("ge", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not greater than or equal to the second.
+///
+/// This is the `Result`-returning version of `chek::greater_or_equal!`.
+/// Requires that the values implement `PartialOrd`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`).
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_greater_or_equal!(4, 4);
+///     chek::ensure_greater_or_equal!(3, 4);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_greater_or_equal!(3, 4, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_greater_or_equal {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_greater_or_equal!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_greater_or_equal!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not greater than or equal to the second.
+///
+/// This is the `Result`-returning version of `chek::ge!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_ge!(4, 4);
+///     chek::ensure_ge!(3, 4);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_ge!(3, 4, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_ge {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_ge!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_ge!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,78 @@
+use crate::Failure;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// A function which is called instead of panicking when an assertion fails.
+/// See `chek::set_failure_handler`.
+pub type FailureHandler = fn(&Failure);
+
+// A `FailureHandler` cast to a pointer, or null if none is installed. There's no
+// `AtomicFnPtr`, but function pointers and data pointers are the same size on
+// every platform we support.
+static HANDLER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Installs a handler to be called when an assertion fails, instead of
+/// panicking. Returns the previously installed handler, if any. Passing `None`
+/// restores the default behavior, which is to panic.
+///
+/// This is intended for environments where panicking isn't what you want, such
+/// as firmware, where the handler could log the failure to a ring buffer, bump a
+/// counter, or reset the device. It works without `std`, and regardless of the
+/// `inline_panics` feature.
+///
+/// This only needs atomic loads and stores, so it's usable on targets without
+/// compare-and-swap. As a consequence, concurrent calls to this function may
+/// both return the same previous handler.
+///
+/// The handler applies to the panicking assertion macros (e.g. `chek::lt!`,
+/// `chek::debug_almost_zero!`, `chek::check!`), but not to the `try_` or
+/// `ensure_` versions, which don't panic anyway.
+///
+/// **Note**: If the handler returns, execution continues after the failed
+/// assertion, as if it had passed. If code after the assertion relies on it
+/// holding, the handler should not return.
+///
+/// # Example
+///
+/// ```rust
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static FAILURES: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn count_failure(failure: &chek::Failure) {
+///     FAILURES.fetch_add(1, Ordering::Relaxed);
+///     println!("{} (at {})", failure, failure.location());
+/// }
+///
+/// chek::set_failure_handler(Some(count_failure));
+/// chek::lt!(4, 3);
+/// chek::almost_zero!(1.0);
+/// assert_eq!(FAILURES.load(Ordering::Relaxed), 2);
+///
+/// // Go back to panicking.
+/// chek::set_failure_handler(None);
+/// ```
+pub fn set_failure_handler(handler: Option<FailureHandler>) -> Option<FailureHandler> {
+    let ptr = match handler {
+        Some(handler) => handler as *mut (),
+        None => core::ptr::null_mut(),
+    };
+    let prev = HANDLER.load(Ordering::Acquire);
+    HANDLER.store(ptr, Ordering::Release);
+    from_ptr(prev)
+}
+
+#[inline]
+pub(crate) fn failure_handler() -> Option<FailureHandler> {
+    from_ptr(HANDLER.load(Ordering::Acquire))
+}
+
+#[inline]
+fn from_ptr(ptr: *mut ()) -> Option<FailureHandler> {
+    if ptr.is_null() {
+        None
+    } else {
+        // Safety: Non-null values only come from `set_failure_handler`, which
+        // stores a valid `FailureHandler`.
+        Some(unsafe { core::mem::transmute::<*mut (), FailureHandler>(ptr) })
+    }
+}
@@ -0,0 +1,138 @@
+//! Hex dumps for `chek::bytes_eq!` failures. These stream straight into the
+//! formatter, so they don't need `alloc`.
+
+use core::fmt;
+
+/// Bytes shown per row, for each side.
+const ROW: usize = 8;
+
+/// At most this many rows are shown, starting just before the first difference.
+const MAX_ROWS: usize = 8;
+
+/// Where two byte buffers compared by `chek::bytes_eq!` first differ.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct BytesMismatch {
+    offset: usize,
+    left_len: usize,
+    right_len: usize,
+}
+
+/// Returns where `left` and `right` first differ, or `None` if they're equal.
+#[doc(hidden)]
+#[inline]
+pub fn bytes_mismatch(left: &[u8], right: &[u8]) -> Option<BytesMismatch> {
+    let (left_len, right_len) = (left.len(), right.len());
+    match left.iter().zip(right).position(|(a, b)| a != b) {
+        Some(offset) => Some(BytesMismatch { offset, left_len, right_len }),
+        None if left_len != right_len => Some(BytesMismatch { offset: left_len.min(right_len), left_len, right_len }),
+        None => None,
+    }
+}
+
+/// Shown in place of the value of each buffer, which would usually be too long
+/// to be useful.
+#[doc(hidden)]
+pub struct ByteCount(pub usize);
+
+impl fmt::Debug for ByteCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => f.write_str("<1 byte>"),
+            n => write!(f, "<{} bytes>", n),
+        }
+    }
+}
+
+/// The `result:` line of a `chek::bytes_eq!` failure, followed by a hex dump of
+/// both buffers around the first difference, side by side, with every
+/// differing byte marked.
+#[doc(hidden)]
+pub struct HexDiff<'a>(pub &'a [u8], pub &'a [u8], pub BytesMismatch);
+
+impl fmt::Display for HexDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let HexDiff(left, right, BytesMismatch { offset, left_len, right_len }) = *self;
+        write!(f, "\nresult: first difference at offset {} ({:#x}), ", offset, offset)?;
+        if left_len == right_len {
+            write!(f, "both have length {}", left_len)?;
+        } else {
+            write!(f, "lengths are {} and {}", left_len, right_len)?;
+        }
+
+        let total_rows = left_len.max(right_len).div_ceil(ROW);
+        let first_row = (offset / ROW).saturating_sub(1);
+        let end_row = (first_row + MAX_ROWS).min(total_rows);
+        if first_row > 0 {
+            f.write_str("\n...")?;
+        }
+        for row in first_row..end_row {
+            let start = row * ROW;
+            let (left, right) = (row_bytes(left, start), row_bytes(right, start));
+            write!(f, "\n{:08x}:", start)?;
+            write_side(f, left, true)?;
+            f.write_str(" |")?;
+            write_side(f, right, false)?;
+            if (0..ROW).any(|i| left.get(i) != right.get(i)) {
+                write_markers(f, left, right)?;
+            }
+        }
+        if end_row < total_rows {
+            f.write_str("\n...")?;
+        }
+        Ok(())
+    }
+}
+
+fn row_bytes(bytes: &[u8], start: usize) -> &[u8] {
+    &bytes[start.min(bytes.len())..(start + ROW).min(bytes.len())]
+}
+
+/// Writes one side of a row, e.g. ` 68 65 6c 6c 6f 0a        hello.`. Unless
+/// `pad` is set, this doesn't write any trailing spaces.
+fn write_side(f: &mut fmt::Formatter<'_>, bytes: &[u8], pad: bool) -> fmt::Result {
+    if bytes.is_empty() && !pad {
+        return Ok(());
+    }
+    for i in 0..ROW {
+        match bytes.get(i) {
+            Some(b) => write!(f, " {:02x}", b)?,
+            None => f.write_str("   ")?,
+        }
+    }
+    f.write_str("  ")?;
+    for i in 0..ROW {
+        match bytes.get(i) {
+            Some(&b) if b.is_ascii_graphic() || b == b' ' => write!(f, "{}", b as char)?,
+            Some(_) => f.write_str(".")?,
+            None if pad => f.write_str(" ")?,
+            None => break,
+        }
+    }
+    Ok(())
+}
+
+/// Writes a line of `^` under each differing byte of the row above, on both
+/// sides.
+fn write_markers(f: &mut fmt::Formatter<'_>, left: &[u8], right: &[u8]) -> fmt::Result {
+    let differs = |i: usize| left.get(i) != right.get(i);
+    let hex = |f: &mut fmt::Formatter<'_>| {
+        for i in 0..ROW {
+            f.write_str(if differs(i) { " ^^" } else { "   " })?;
+        }
+        f.write_str("  ")
+    };
+    f.write_str("\n         ")?;
+    hex(f)?;
+    for i in 0..ROW {
+        f.write_str(if differs(i) { "^" } else { " " })?;
+    }
+    f.write_str("  ")?;
+    hex(f)?;
+    // Leave off trailing spaces.
+    let last = (0..ROW).rev().find(|&i| differs(i)).unwrap_or(0);
+    for i in 0..=last {
+        f.write_str(if differs(i) { "^" } else { " " })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! The `chek::IntCmp` trait, which lets the comparison assertions (e.g.
+//! `chek::lt!`) compare integers of different types.
+
+use core::cmp::Ordering;
+
+/// Compares integers by their mathematical values, even if they're of
+/// different types.
+///
+/// This is implemented for every pair of primitive integer types, and is what
+/// `chek::lt!`, `chek::eq!` and the other comparison assertions use to compare
+/// primitive integers of different types. Unlike comparing after an `as` cast, it can't
+/// get the wrong answer, e.g. `-1i32` is less than `0u64`, even though `-1i32 as
+/// u64` is `u64::MAX`.
+///
+/// # Example
+///
+/// ```rust
+/// use chek::IntCmp;
+/// use core::cmp::Ordering;
+///
+/// assert_eq!((-1i32).int_cmp(&0u64), Ordering::Less);
+/// assert_eq!(u64::MAX.int_cmp(&-1i8), Ordering::Greater);
+/// assert_eq!(255u8.int_cmp(&255i64), Ordering::Equal);
+///
+/// let (offset, len): (i64, usize) = (-3, 10);
+/// chek::lt!(offset, len);
+/// chek::ne!(offset, len);
+/// ```
+pub trait IntCmp<Rhs: ?Sized = Self> {
+    /// Compares `self` and `other` as mathematical integers.
+    fn int_cmp(&self, other: &Rhs) -> Ordering;
+}
+
+/// Any primitive integer's value. Negative values are less than non-negative
+/// ones, which is the order the variants are declared in.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Wide {
+    Negative(i128),
+    NonNegative(u128),
+}
+
+trait Widen: Copy {
+    fn widen(self) -> Wide;
+}
+
+macro_rules! impl_widen {
+    (signed: $($s:ty),*; unsigned: $($u:ty),*) => {
+        $(impl Widen for $s {
+            #[inline]
+            fn widen(self) -> Wide {
+                if self < 0 {
+                    Wide::Negative(self as i128)
+                } else {
+                    Wide::NonNegative(self as u128)
+                }
+            }
+        })*
+        $(impl Widen for $u {
+            #[inline]
+            fn widen(self) -> Wide {
+                Wide::NonNegative(self as u128)
+            }
+        })*
+    };
+}
+
+impl_widen!(signed: i8, i16, i32, i64, i128, isize; unsigned: u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_int_cmp {
+    ($($left:ty),*) => {
+        $(impl_int_cmp!(@with $left; i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);)*
+    };
+    (@with $left:ty; $($right:ty),*) => {
+        $(impl IntCmp<$right> for $left {
+            #[inline]
+            fn int_cmp(&self, other: &$right) -> Ordering {
+                self.widen().cmp(&other.widen())
+            }
+        })*
+    };
+}
+
+impl_int_cmp!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
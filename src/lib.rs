@@ -9,14 +9,47 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 // Need to access these from inside macros.
 #[doc(hidden)]
 pub use almost;
+#[doc(hidden)]
+pub use chek_macros::{__check, __ordered};
 
 #[doc(hidden)]
 #[macro_use]
 pub mod __internal;
 
+mod failure;
+pub use failure::Failure;
+
+mod approx;
+pub use approx::{AlmostEq, Mismatch, MismatchPath};
+/// Derives `chek::AlmostEq` for a struct, comparing it field by field. Each
+/// field must implement `chek::AlmostEq` itself.
+pub use chek_macros::AlmostEq;
+
+mod cmp_pair;
+mod int_cmp;
+pub use int_cmp::IntCmp;
+
+mod collection;
+pub use collection::{Contains, Location};
+
+mod handler;
+
+#[cfg(feature = "diff")]
+mod diff;
+mod float;
+mod hex;
+mod maybe_debug;
+mod multiset;
+pub use handler::{set_failure_handler, FailureHandler};
+
 // These are separate modules for easier copy/paste...
 // TODO: can we remove duplication without making the docs worse?
 
@@ -35,8 +68,36 @@ mod ne;
 #[macro_use]
 mod almost_eq;
 #[macro_use]
+mod tolerance;
+#[macro_use]
+mod all_almost;
+#[macro_use]
+mod exact;
+#[macro_use]
 mod almost_zero;
 #[macro_use]
 mod not_almost_zero;
 #[macro_use]
+mod classify;
+#[macro_use]
+mod slice_eq;
+#[macro_use]
+mod bytes_eq;
+#[macro_use]
+mod unwrap;
+#[macro_use]
+mod matches;
+#[macro_use]
+mod range;
+#[macro_use]
+mod contains;
+#[macro_use]
+mod same_elements;
+#[macro_use]
+mod sorted;
+#[macro_use]
 mod unreachable;
+#[macro_use]
+mod check;
+#[macro_use]
+mod ordered;
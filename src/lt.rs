@@ -1,7 +1,10 @@
 
 /// Panics if the first expression is not strictly less than the second.
 ///
-/// Requires that the values implement `PartialOrd` and `Debug`.
+/// Requires that the values implement `PartialOrd`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`).
 ///
 /// Note: is also aliased as `chek::lt!`. A debug-only version is available as
 /// `chek::debug_less!`.
@@ -20,21 +23,24 @@
 macro_rules! less {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left < right) {
-            $crate::__cmp_assert_fail!("less", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            $crate::__ord_assert_fail!("less", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left < right) {
-            $crate::__cmp_assert_fail!("less", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_lt, left, right) {
+            $crate::__ord_assert_fail!("less", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
 
 /// Panics if the first expression is not strictly less than the second.
 ///
-/// Requires that the values implement `PartialOrd` and `Debug`.
+/// Requires that the values implement `PartialOrd`. Values that don't implement
+/// `Debug` are shown as `<type name: Debug not available here>` on failure.
+/// Primitive integers of different types can also be compared, by value (see
+/// `chek::IntCmp`).
 ///
 /// Note: is also aliased as `chek::less!`. A debug-only version is available as
 /// `chek::debug_lt!`.
@@ -57,14 +63,14 @@ macro_rules! less {
 macro_rules! lt {
     ($left:expr, $right:expr $(,)?) => ({
         let (left, right) = (&($left), &($right));
-        if !(left < right) {
-            $crate::__cmp_assert_fail!("lt", left, right, stringify!($left), stringify!($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            $crate::__ord_assert_fail!("lt", left, right, stringify!($left), stringify!($right));
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         let (left, right) = (&($left), &($right));
-        if !(left < right) {
-            $crate::__cmp_assert_fail!("lt", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+        if !$crate::__cmp!(is_lt, left, right) {
+            $crate::__ord_assert_fail!("lt", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
         }
     })
 }
@@ -93,16 +99,16 @@ macro_rules! debug_less {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left < right) {
-                $crate::__cmp_assert_fail!("debug_less", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_lt, left, right) {
+                $crate::__ord_assert_fail!("debug_less", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left < right) {
-                $crate::__cmp_assert_fail!("debug_less", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_lt, left, right) {
+                $crate::__ord_assert_fail!("debug_less", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
@@ -132,18 +138,196 @@ macro_rules! debug_lt {
     ($left:expr, $right:expr $(,)?) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left < right) {
-                $crate::__cmp_assert_fail!("debug_lt", left, right, stringify!($left), stringify!($right));
+            if !$crate::__cmp!(is_lt, left, right) {
+                $crate::__ord_assert_fail!("debug_lt", left, right, stringify!($left), stringify!($right));
             }
         }
     });
     ($left:expr, $right:expr, $($msg_args:tt)+) => ({
         if cfg!(debug_assertions) {
             let (left, right) = (&($left), &($right));
-            if !(left < right) {
-                $crate::__cmp_assert_fail!("debug_lt", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
+            if !$crate::__cmp!(is_lt, left, right) {
+                $crate::__ord_assert_fail!("debug_lt", left, right, stringify!($left), stringify!($right), format_args!($($msg_args)+))
             }
         }
     })
 }
 
+
+/// Returns `Err(chek::Failure)` if the first expression is not strictly less
+/// than the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::less!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// Note: is also aliased as `chek::try_lt!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_less!(3, 4).is_ok());
+/// assert!(chek::try_less!(4, 3).is_err());
+/// assert!(chek::try_less!(4, 3, "With a message").is_err());
+/// assert!(chek::try_less!(4, 3, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_less {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            Err($crate::__internal::ord_failure("less", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            Err($crate::__internal::ord_failure("less", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns `Err(chek::Failure)` if the first expression is not strictly less
+/// than the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::lt!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// Note: is also aliased as `chek::try_less!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_lt!(3, 4).is_ok());
+/// assert!(chek::try_lt!(4, 3).is_err());
+/// assert!(chek::try_lt!(4, 3, "With a message").is_err());
+/// assert!(chek::try_lt!(4, 3, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            Err($crate::__internal::ord_failure("lt", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if !$crate::__cmp!(is_lt, left, right) {
+            Err($crate::__internal::ord_failure("lt", $crate::__debug!(left), $crate::__debug!(right), stringify!($left), stringify!($right), $crate::__cmp!(ordering, left, right), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not strictly less than the second.
+///
+/// This is the `Result`-returning version of `chek::less!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_less!(3, 4);
+///     chek::ensure_less!(4, 3);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_less!(4, 3, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_less {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_less!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_less!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not strictly less than the second.
+///
+/// This is the `Result`-returning version of `chek::lt!`. Requires that the
+/// values implement `PartialOrd`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_lt!(3, 4);
+///     chek::ensure_lt!(4, 3);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_lt!(4, 3, "With an error");
+///     Ok(())
+/// }
+///
+/// #[derive(Debug)]
+/// enum MyError {
+///     TooBig(chek::Failure),
+/// }
+///
+/// fn with_failure(x: i32) -> Result<(), MyError> {
+///     chek::ensure_lt!(x, 3, MyError::TooBig);
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// let MyError::TooBig(failure) = with_failure(4).unwrap_err();
+/// assert!(failure.to_string().contains("left: `4` = `x`"));
+/// ```
+#[macro_export]
+macro_rules! ensure_lt {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_lt!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_lt!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,422 @@
+
+/// Panics if the value doesn't match the pattern.
+///
+/// Like `assert!(matches!(value, pattern))`, but the failure message includes
+/// the `Debug` output of the value, along with the source of both the value and
+/// the pattern. As with `matches!`, the pattern may have `|` alternatives and an
+/// `if` guard. The value is matched by reference, so it isn't consumed, but any
+/// bindings used in the guard need to be `Copy` (or bound with `ref`).
+///
+/// Alternatively, the pattern may be followed by `=> expr`, in which case the
+/// value is consumed, and `expr` is evaluated with the pattern's bindings and
+/// returned. If a failure handler is installed (see `chek::set_failure_handler`)
+/// and it returns, this form panics anyway, since there's no value to return.
+///
+/// If the value doesn't implement `Debug`, it's shown as
+/// `<type name: Debug not available here>` on failure.
+///
+/// A debug-only version is available as `chek::debug_matches!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(Debug)]
+/// struct Pair(i32, i32);
+///
+/// let v = Some(Pair(1, 2));
+/// chek::matches!(v, Some(Pair(1.., _)));
+/// chek::matches!(v, None | Some(Pair(_, 2)), "With a message");
+/// chek::matches!(v, Some(Pair(a, b)) if a < b, "With a formatted message: {}", "oh no");
+/// let (a, b) = chek::matches!(v, Some(Pair(a, b)) => (a, b));
+/// assert_eq!(a + b, 3);
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let v: Result<u8, &str> = Err("timed out");
+/// // assertion failed: `chek::matches!(v, Ok(0..=9))`
+/// //  value: `Err("timed out")`
+/// chek::matches!(v, Ok(0..=9));
+/// ```
+#[macro_export]
+macro_rules! matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr $(,)?) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => $bindings,
+            ref value => {
+                $crate::__match_assert_fail!("matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?));
+                $crate::__internal::no_value("matches")
+            }
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr, $($msg_args:tt)+) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => $bindings,
+            ref value => {
+                $crate::__match_assert_fail!("matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+));
+                $crate::__internal::no_value("matches")
+            }
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => {}
+                _ => $crate::__match_assert_fail!("matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?)),
+            },
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => {}
+                _ => $crate::__match_assert_fail!("matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+)),
+            },
+        }
+    });
+}
+
+/// Same as `chek::matches!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace, except for the `=> expr` form, which still has to
+/// match the value, and panics without a message if it doesn't.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v: Result<u8, &str> = Ok(3);
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_matches!(v, Ok(0..=9));
+/// chek::debug_matches!(v, Ok(x) if x % 2 == 1, "With a message");
+/// // This one isn't.
+/// let x = chek::debug_matches!(v, Ok(x) => x);
+/// assert_eq!(x, 3);
+/// ```
+#[macro_export]
+macro_rules! debug_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr $(,)?) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => $bindings,
+            ref value => {
+                if cfg!(debug_assertions) {
+                    $crate::__match_assert_fail!("debug_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?));
+                }
+                $crate::__internal::no_value("debug_matches")
+            }
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr, $($msg_args:tt)+) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => $bindings,
+            ref value => {
+                if cfg!(debug_assertions) {
+                    $crate::__match_assert_fail!("debug_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+));
+                }
+                $crate::__internal::no_value("debug_matches")
+            }
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        if cfg!(debug_assertions) {
+            match $value {
+                ref value => match *value {
+                    $($pat)|+ $(if $guard)? => {}
+                    _ => $crate::__match_assert_fail!("debug_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?)),
+                },
+            }
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            match $value {
+                ref value => match *value {
+                    $($pat)|+ $(if $guard)? => {}
+                    _ => $crate::__match_assert_fail!("debug_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+)),
+                },
+            }
+        }
+    });
+}
+
+/// Panics if the value matches the pattern.
+///
+/// Like `assert!(!matches!(value, pattern))`, but the failure message includes
+/// the `Debug` output of the value, along with the source of both the value and
+/// the pattern. As with `matches!`, the pattern may have `|` alternatives and an
+/// `if` guard. The value is matched by reference, so it isn't consumed, but any
+/// bindings used in the guard need to be `Copy` (or bound with `ref`).
+///
+/// If the value doesn't implement `Debug`, it's shown as
+/// `<type name: Debug not available here>` on failure.
+///
+/// A debug-only version is available as `chek::debug_not_matches!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v: Result<u8, &str> = Ok(3);
+/// chek::not_matches!(v, Err(_));
+/// chek::not_matches!(v, Ok(0) | Err(_), "With a message");
+/// chek::not_matches!(v, Ok(x) if x > 9, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! not_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => $crate::__match_assert_fail!("not_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?)),
+                _ => {}
+            },
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => $crate::__match_assert_fail!("not_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+)),
+                _ => {}
+            },
+        }
+    });
+}
+
+/// Same as `chek::not_matches!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v: Result<u8, &str> = Ok(3);
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_not_matches!(v, Err(_));
+/// chek::debug_not_matches!(v, Ok(x) if x > 9, "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_not_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        if cfg!(debug_assertions) {
+            match $value {
+                ref value => match *value {
+                    $($pat)|+ $(if $guard)? => $crate::__match_assert_fail!("debug_not_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?)),
+                    _ => {}
+                },
+            }
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            match $value {
+                ref value => match *value {
+                    $($pat)|+ $(if $guard)? => $crate::__match_assert_fail!("debug_not_matches", value, stringify!($value), stringify!($($pat)|+ $(if $guard)?), format_args!($($msg_args)+)),
+                    _ => {}
+                },
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the value doesn't match the pattern, and
+/// `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::matches!`. As with it, the
+/// pattern may have `|` alternatives and an `if` guard, and may be followed by
+/// `=> expr`, in which case the value is consumed, and `Ok(expr)` is returned
+/// instead.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v: Result<u8, &str> = Ok(3);
+/// assert!(chek::try_matches!(v, Ok(0..=9)).is_ok());
+/// assert!(chek::try_matches!(v, Err(_)).is_err());
+/// assert!(chek::try_matches!(v, Ok(x) if x > 9, "With a message").is_err());
+/// assert_eq!(chek::try_matches!(v, Ok(x) => x * 2).ok(), Some(6));
+/// assert!(chek::try_matches!(v, Err(e) => e, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr $(,)?) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => Ok($bindings),
+            ref value => Err($crate::__internal::match_failure("matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), None)),
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr, $($msg_args:tt)+) => (
+        match $value {
+            $($pat)|+ $(if $guard)? => Ok($bindings),
+            ref value => Err($crate::__internal::match_failure("matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), Some(format_args!($($msg_args)+)))),
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => Ok(()),
+                _ => Err($crate::__internal::match_failure("matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), None)),
+            },
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => Ok(()),
+                _ => Err($crate::__internal::match_failure("matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), Some(format_args!($($msg_args)+)))),
+            },
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the value doesn't
+/// match the pattern.
+///
+/// This is the `Result`-returning version of `chek::matches!`. As with it, the
+/// pattern may have `|` alternatives and an `if` guard, and may be followed by
+/// `=> expr`, in which case the value is consumed, and `expr` is evaluated with
+/// the pattern's bindings and returned.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(v: Result<u8, &str>) -> Result<u8, chek::Failure> {
+///     chek::ensure_matches!(v, Ok(0..=9));
+///     Ok(chek::ensure_matches!(v, Ok(x) => x * 2))
+/// }
+///
+/// fn with_error(v: Result<u8, &str>) -> Result<(), &'static str> {
+///     chek::ensure_matches!(v, Ok(x) if x > 9, "With an error");
+///     Ok(())
+/// }
+///
+/// assert_eq!(no_error(Ok(3)).ok(), Some(6));
+/// assert!(no_error(Err("timed out")).is_err());
+/// assert_eq!(with_error(Ok(3)), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr $(,)?) => (
+        match $crate::try_matches!($value, $($pat)|+ $(if $guard)? => $bindings) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from(failure)),
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? => $bindings:expr, $err:expr $(,)?) => (
+        match $crate::try_matches!($value, $($pat)|+ $(if $guard)? => $bindings) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from($crate::__ensure_error!($err, failure))),
+        }
+    );
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        if let Err(failure) = $crate::try_matches!($value, $($pat)|+ $(if $guard)?) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_matches!($value, $($pat)|+ $(if $guard)?) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the value matches the pattern, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::not_matches!`. As with it, the
+/// pattern may have `|` alternatives and an `if` guard.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v: Result<u8, &str> = Ok(3);
+/// assert!(chek::try_not_matches!(v, Err(_)).is_ok());
+/// assert!(chek::try_not_matches!(v, Ok(0..=9)).is_err());
+/// assert!(chek::try_not_matches!(v, Ok(x) if x < 9, "With a message").is_err());
+/// assert!(chek::try_not_matches!(v, Ok(3), "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => Err($crate::__internal::match_failure("not_matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), None)),
+                _ => Ok(()),
+            },
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $($msg_args:tt)+) => ({
+        match $value {
+            ref value => match *value {
+                $($pat)|+ $(if $guard)? => Err($crate::__internal::match_failure("not_matches", $crate::__debug!(value), stringify!($value), stringify!($($pat)|+ $(if $guard)?), Some(format_args!($($msg_args)+)))),
+                _ => Ok(()),
+            },
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the value
+/// matches the pattern.
+///
+/// This is the `Result`-returning version of `chek::not_matches!`. As with it,
+/// the pattern may have `|` alternatives and an `if` guard.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(v: Result<u8, &str>) -> Result<(), chek::Failure> {
+///     chek::ensure_not_matches!(v, Err(_));
+///     Ok(())
+/// }
+///
+/// fn with_error(v: Result<u8, &str>) -> Result<(), &'static str> {
+///     chek::ensure_not_matches!(v, Ok(x) if x < 9, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error(Ok(3)).is_ok());
+/// assert!(no_error(Err("timed out")).is_err());
+/// assert_eq!(with_error(Ok(3)), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_matches {
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)? $(,)?) => ({
+        if let Err(failure) = $crate::try_not_matches!($value, $($pat)|+ $(if $guard)?) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $($pat:pat)|+ $(if $guard:expr)?, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_matches!($value, $($pat)|+ $(if $guard)?) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,52 @@
+//! How the assertions print values that might not implement `Debug`.
+//!
+//! `chek::__debug!` wraps a reference to a reference in a `MaybeDebug`, and
+//! calls `as_debug` on `&&MaybeDebug`. If the value implements `Debug`, method
+//! resolution picks `DebugValue`, which returns the inner reference, so that
+//! unsized values like slices work too. Otherwise it falls back to
+//! `NoDebugValue`, which returns a placeholder that prints the type's name,
+//! like `<my_crate::Handle: Debug not available here>`. This is the same
+//! "autoref specialization" trick as in `cmp_pair.rs`.
+//!
+//! The placeholder doesn't say the type has no `Debug` impl, since it's also
+//! used for values of a generic type that isn't bounded by `Debug`.
+
+use core::any::type_name;
+use core::fmt::{self, Debug};
+use core::marker::PhantomData;
+
+#[doc(hidden)]
+pub struct MaybeDebug<'a, 'b, T: ?Sized>(pub &'a &'b T);
+
+#[doc(hidden)]
+pub trait DebugValue<'a> {
+    fn as_debug(&self) -> &'a dyn Debug;
+}
+
+impl<'a, 'b, T: Debug + ?Sized> DebugValue<'a> for &MaybeDebug<'a, 'b, T> {
+    #[inline]
+    fn as_debug(&self) -> &'a dyn Debug {
+        self.0
+    }
+}
+
+#[doc(hidden)]
+pub trait NoDebugValue<'a> {
+    fn as_debug(&self) -> &'a dyn Debug;
+}
+
+impl<'a, 'b, T: ?Sized + 'a> NoDebugValue<'a> for MaybeDebug<'a, 'b, T> {
+    #[inline]
+    fn as_debug(&self) -> &'a dyn Debug {
+        &NoDebug::<T>(PhantomData)
+    }
+}
+
+/// Stands in for a value of type `T`, which doesn't implement `Debug`.
+struct NoDebug<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> Debug for NoDebug<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}: Debug not available here>", type_name::<T>())
+    }
+}
@@ -0,0 +1,248 @@
+//! How `chek::same_elements!` compares two slices as multisets, and reports
+//! the elements whose counts differ.
+//!
+//! The slices are wrapped in an `ElementsPair`, and `chek::__same_elements!`
+//! calls `elements_diff` on `&&&ElementsPair`, so that method resolution picks
+//! the first of these that applies (the same "autoref specialization" trick as
+//! in `cmp_pair.rs`):
+//!
+//! 1. With the `std` feature, if the elements are `Hash + Eq`, count them in a
+//!    `HashMap`.
+//! 2. With the `alloc` feature, if the elements are `Ord`, sort references to
+//!    them, and count runs of equal elements.
+//! 3. Otherwise, count each element by scanning both slices for it, which is
+//!    O(n²), but only needs `PartialEq`, and doesn't allocate.
+//!
+//! Either way, the differences are listed in the order the elements first
+//! appear in (the left slice, then the right), so the output doesn't depend
+//! on which of these was used.
+
+use core::fmt::{self, Debug};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// How many elements are listed on each line of the report, before the rest
+/// are summarized as `... and N more`.
+const MAX_LISTED: usize = 8;
+
+#[doc(hidden)]
+pub struct ElementsPair<'a, T>(&'a [T], &'a [T]);
+
+/// Used by `chek::same_elements!`. This takes `AsRef` rather than just slices
+/// so that `&Vec<T>` and `&[T; N]` work too.
+#[doc(hidden)]
+#[inline]
+pub fn elements_pair<'a, L, R, T>(left: &'a L, right: &'a R) -> ElementsPair<'a, T>
+where
+    L: AsRef<[T]> + ?Sized,
+    R: AsRef<[T]> + ?Sized,
+{
+    ElementsPair(left.as_ref(), right.as_ref())
+}
+
+/// An element that occurs a different number of times in each slice.
+#[doc(hidden)]
+pub struct CountDiff<'a, T> {
+    element: &'a T,
+    left: usize,
+    right: usize,
+}
+
+// Not derived, since that would require `T: Copy`.
+impl<T> Clone for CountDiff<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CountDiff<'_, T> {}
+
+/// The elements whose counts differ between two slices compared by
+/// `chek::same_elements!`. Formats as the `missing:`, `extra:` and `counts:`
+/// lines of the failure message.
+#[doc(hidden)]
+pub enum ElementsDiff<'a, T> {
+    #[cfg(feature = "alloc")]
+    Listed(Vec<CountDiff<'a, T>>),
+    Scanned(&'a [T], &'a [T]),
+}
+
+// The traits exist without the features their impls need, so that
+// `chek::__same_elements!` can import them either way.
+#[doc(hidden)]
+pub trait HashElements<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>>;
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: core::hash::Hash + Eq> HashElements<'a, T> for &&ElementsPair<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>> {
+        let ElementsPair(left, right) = ***self;
+        // For each element: its count in each slice, and when it first appeared.
+        let mut counts = std::collections::HashMap::<&T, (usize, usize, usize)>::new();
+        for (i, element) in left.iter().enumerate() {
+            counts.entry(element).or_insert((0, 0, i)).0 += 1;
+        }
+        for (i, element) in right.iter().enumerate() {
+            counts.entry(element).or_insert((0, 0, left.len() + i)).1 += 1;
+        }
+        let mut diffs: Vec<_> = counts.into_iter().filter(|(_, (l, r, _))| l != r).collect();
+        diffs.sort_unstable_by_key(|&(_, (_, _, first))| first);
+        listed(diffs.into_iter().map(|(element, (left, right, _))| CountDiff { element, left, right }).collect())
+    }
+}
+
+#[doc(hidden)]
+pub trait OrdElements<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> OrdElements<'a, T> for &ElementsPair<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>> {
+        let ElementsPair(left, right) = **self;
+        // Each element, with the index it appeared at (counting the right
+        // slice's after the left's). After a stable sort, the first of each
+        // run of equal elements has the index it first appeared at.
+        let mut all: Vec<(&T, usize)> = left.iter().chain(right).zip(0..).collect();
+        all.sort_by(|a, b| a.0.cmp(b.0));
+        let mut diffs = Vec::new();
+        let mut rest = &all[..];
+        while let Some(&(element, first)) = rest.first() {
+            let run = rest.iter().take_while(|(x, _)| *x == element).count();
+            let left_count = rest[..run].iter().filter(|&&(_, i)| i < left.len()).count();
+            if left_count * 2 != run {
+                diffs.push((first, CountDiff { element, left: left_count, right: run - left_count }));
+            }
+            rest = &rest[run..];
+        }
+        diffs.sort_unstable_by_key(|&(first, _)| first);
+        listed(diffs.into_iter().map(|(_, diff)| diff).collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn listed<T>(diffs: Vec<CountDiff<'_, T>>) -> Option<ElementsDiff<'_, T>> {
+    if diffs.is_empty() {
+        None
+    } else {
+        Some(ElementsDiff::Listed(diffs))
+    }
+}
+
+#[doc(hidden)]
+pub trait ScanElements<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>>;
+}
+
+impl<'a, T: PartialEq> ScanElements<'a, T> for ElementsPair<'a, T> {
+    fn elements_diff(&self) -> Option<ElementsDiff<'a, T>> {
+        let ElementsPair(left, right) = *self;
+        if left.len() == right.len() && Scan::new(left, right).next().is_none() {
+            None
+        } else {
+            Some(ElementsDiff::Scanned(left, right))
+        }
+    }
+}
+
+/// Finds the elements whose counts differ without allocating, by counting
+/// each element the first time it's seen. An element that isn't equal to
+/// itself (like NaN) can't be found in the other slice, so it's reported as
+/// missing or extra on its own.
+struct Scan<'a, T> {
+    left: &'a [T],
+    right: &'a [T],
+    // Index into the left slice, then the right.
+    next: usize,
+}
+
+impl<T> Clone for Scan<'_, T> {
+    fn clone(&self) -> Self {
+        Scan { left: self.left, right: self.right, next: self.next }
+    }
+}
+
+impl<'a, T> Scan<'a, T> {
+    fn new(left: &'a [T], right: &'a [T]) -> Self {
+        Scan { left, right, next: 0 }
+    }
+}
+
+impl<'a, T: PartialEq> Iterator for Scan<'a, T> {
+    type Item = CountDiff<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let count = |slice: &[T], element: &T| slice.iter().filter(|x| *x == element).count();
+        let (left, right) = (self.left, self.right);
+        while self.next < left.len() + right.len() {
+            let i = self.next;
+            self.next += 1;
+            let (element, seen_before) = if i < left.len() {
+                (&left[i], left[..i].contains(&left[i]))
+            } else {
+                let j = i - left.len();
+                (&right[j], left.contains(&right[j]) || right[..j].contains(&right[j]))
+            };
+            if seen_before {
+                continue;
+            }
+            #[allow(clippy::eq_op)]
+            if element != element {
+                let in_left = (i < left.len()) as usize;
+                return Some(CountDiff { element, left: in_left, right: 1 - in_left });
+            }
+            let diff = CountDiff { element, left: count(left, element), right: count(right, element) };
+            if diff.left != diff.right {
+                return Some(diff);
+            }
+        }
+        None
+    }
+}
+
+impl<T: PartialEq + Debug> fmt::Display for ElementsDiff<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "alloc")]
+            ElementsDiff::Listed(diffs) => fmt_diffs(f, diffs.iter().copied()),
+            ElementsDiff::Scanned(left, right) => fmt_diffs(f, Scan::new(left, right)),
+        }
+    }
+}
+
+fn fmt_diffs<'a, T: Debug + 'a>(f: &mut fmt::Formatter<'_>, diffs: impl Iterator<Item = CountDiff<'a, T>> + Clone) -> fmt::Result {
+    let missing = diffs.clone().filter(|d| d.left == 0);
+    fmt_line(f, "\nmissing: ", missing, |f, d| fmt_count(f, d.element, d.right))?;
+    let extra = diffs.clone().filter(|d| d.right == 0);
+    fmt_line(f, "\n  extra: ", extra, |f, d| fmt_count(f, d.element, d.left))?;
+    let counts = diffs.filter(|d| d.left != 0 && d.right != 0);
+    fmt_line(f, "\n counts: ", counts, |f, d| write!(f, "`{:?}` (left {}, right {})", d.element, d.left, d.right))
+}
+
+/// Writes `label` followed by the first `MAX_LISTED` items, or nothing if there
+/// aren't any.
+fn fmt_line<'a, T: 'a>(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    mut items: impl Iterator<Item = CountDiff<'a, T>>,
+    mut fmt_item: impl FnMut(&mut fmt::Formatter<'_>, CountDiff<'a, T>) -> fmt::Result,
+) -> fmt::Result {
+    for (i, item) in items.by_ref().take(MAX_LISTED).enumerate() {
+        f.write_str(if i == 0 { label } else { ", " })?;
+        fmt_item(f, item)?;
+    }
+    match items.count() {
+        0 => Ok(()),
+        rest => write!(f, ", ... and {} more", rest),
+    }
+}
+
+fn fmt_count(f: &mut fmt::Formatter<'_>, element: &dyn Debug, count: usize) -> fmt::Result {
+    if count == 1 {
+        write!(f, "`{:?}`", element)
+    } else {
+        write!(f, "`{:?}` (x{})", element, count)
+    }
+}
@@ -128,3 +128,170 @@ macro_rules! debug_not_almost_zero_with {
         }
     });
 }
+
+/// Returns `Err(chek::Failure)` if the provided value is almost equal to zero,
+/// and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::not_almost_zero!`. Uses the [`almost`
+/// crate](https://crates.io/crates/almost). See [the `almost::zero`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.zero.html) for more
+/// details.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_almost_zero!(1.0).is_ok());
+/// assert!(chek::try_not_almost_zero!(0.00000001, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_almost_zero {
+    ($value:expr $(,)?) => ({
+        let value = $value;
+        if $crate::almost::zero(value) {
+            Err($crate::__internal::value_failure("not_almost_zero", &value, stringify!($value), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        let value = $value;
+        if $crate::almost::zero(value) {
+            Err($crate::__internal::value_failure("not_almost_zero", &value, stringify!($value), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the provided value is almost equal to zero,
+/// and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::not_almost_zero_with!`. Uses the [`almost`
+/// crate](https://crates.io/crates/almost). See [the `almost::zero_with`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.zero_with.html) for more
+/// details.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_almost_zero_with!(1.0, 0.01).is_ok());
+/// assert!(chek::try_not_almost_zero_with!(0.00000001, 0.01, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_almost_zero_with {
+    ($value:expr, $tolerance:expr $(,)?) => ({
+        let (value, tolerance) = ($value, $tolerance);
+        if $crate::almost::zero_with(value, tolerance) {
+            Err($crate::__internal::value_failure("not_almost_zero_with", &value, stringify!($value), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($value:expr, $tolerance:expr, $($msg_args:tt)+) => ({
+        let (value, tolerance) = ($value, $tolerance);
+        if $crate::almost::zero_with(value, tolerance) {
+            Err($crate::__internal::value_failure("not_almost_zero_with", &value, stringify!($value), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the provided
+/// value is almost equal to zero.
+///
+/// This is the `Result`-returning version of `chek::not_almost_zero!`. Uses the
+/// [`almost` crate](https://crates.io/crates/almost). See [the `almost::zero`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.zero.html) for more
+/// details.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_almost_zero!(1.0);
+///     chek::ensure_not_almost_zero!(0.00000001);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_almost_zero!(0.00000001, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_almost_zero {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_zero!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_zero!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the provided
+/// value is almost equal to zero.
+///
+/// This is the `Result`-returning version of `chek::not_almost_zero_with!`.
+/// Uses the [`almost` crate](https://crates.io/crates/almost). See [the
+/// `almost::zero_with`
+/// documentation](https://docs.rs/almost/%2a/almost/fn.zero_with.html) for more
+/// details.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_almost_zero_with!(1.0, 0.01);
+///     chek::ensure_not_almost_zero_with!(0.00000001, 0.01);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_almost_zero_with!(0.00000001, 0.01, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_almost_zero_with {
+    ($value:expr, $tolerance:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_zero_with!($value, $tolerance) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $tolerance:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_almost_zero_with!($value, $tolerance) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,131 @@
+
+/// Panics unless every comparison in a chain like `a <= b < c` holds.
+///
+/// Accepts any number of operands separated by `<`, `<=`, `>`, `>=` or `==`,
+/// with the same meaning as in math: `chek::ordered!(a <= b < c)` checks both
+/// `a <= b` and `b < c`. Each operand is evaluated exactly once, left to right.
+/// On failure, the value of every operand is printed, along with the first
+/// comparison in the chain that didn't hold.
+///
+/// Requires that the operands implement `Debug`, and `PartialOrd` (or
+/// `PartialEq` for `==`) with their neighbors. Operands that bind more loosely
+/// than comparisons, such as `a && b` or `a..b`, must be parenthesized.
+///
+/// A debug-only version is available as `chek::debug_ordered!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let (start, cursor, end) = (0, 3, 5);
+/// chek::ordered!(start <= cursor < end);
+/// chek::ordered!(end > cursor >= start, "With a message");
+/// chek::ordered!(0 <= start == 0 < end, "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (start, cursor, end) = (0, 5, 5);
+/// // assertion failed: `chek::ordered!(start <= cursor < end)`
+/// //       start: `0`
+/// //      cursor: `5`
+/// //         end: `5`
+/// //   failed at: `cursor < end`
+/// chek::ordered!(start <= cursor < end);
+/// ```
+#[macro_export]
+macro_rules! ordered {
+    ($($args:tt)+) => {
+        $crate::__ordered!($crate, "ordered", $($args)+)
+    };
+}
+
+/// Same as `chek::ordered!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// let (start, cursor, end) = (0, 3, 5);
+/// chek::debug_ordered!(start <= cursor < end);
+/// chek::debug_ordered!(start <= cursor < end, "With a message");
+/// chek::debug_ordered!(start <= cursor < end, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_ordered {
+    ($($args:tt)+) => {
+        if cfg!(debug_assertions) {
+            $crate::__ordered!($crate, "debug_ordered", $($args)+)
+        }
+    };
+}
+
+/// Returns `Err(chek::Failure)` unless every comparison in a chain like
+/// `a <= b < c` holds, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::ordered!`, and accepts the same
+/// chains.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let (start, cursor, end) = (0, 3, 5);
+/// assert!(chek::try_ordered!(start <= cursor < end).is_ok());
+/// assert!(chek::try_ordered!(start < end <= cursor).is_err());
+/// assert!(chek::try_ordered!(start < end <= cursor, "With a message").is_err());
+/// assert!(chek::try_ordered!(start < end <= cursor, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_ordered {
+    ($($args:tt)+) => {
+        $crate::__ordered!($crate, try "ordered", $($args)+)
+    };
+}
+
+/// Returns early with an error from the enclosing function unless every
+/// comparison in a chain like `a <= b < c` holds.
+///
+/// This is the `Result`-returning version of `chek::ordered!`, and accepts the
+/// same chains.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(start: usize, cursor: usize, end: usize) -> Result<(), chek::Failure> {
+///     chek::ensure_ordered!(start <= cursor < end);
+///     Ok(())
+/// }
+///
+/// fn with_error(start: usize, cursor: usize, end: usize) -> Result<(), &'static str> {
+///     chek::ensure_ordered!(start <= cursor < end, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error(0, 3, 5).is_ok());
+/// assert!(no_error(0, 5, 5).is_err());
+/// assert_eq!(with_error(0, 5, 5), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_ordered {
+    ($($args:tt)+) => {
+        $crate::__ordered!($crate, ensure "ordered", $($args)+)
+    };
+}
@@ -0,0 +1,323 @@
+
+/// Panics if the first expression is not within the range given by the second.
+///
+/// The range can be anything implementing `RangeBounds`, e.g. `..`, `a..`,
+/// `..=b`, `a..b` or `a..=b`. On failure, the message says which bound of the
+/// range was violated.
+///
+/// Requires that the value implements `PartialOrd` and `Debug`, and that the
+/// range implements `Debug`.
+///
+/// A debug-only version is available as `chek::debug_in_range!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::in_range!(3, 0..4);
+/// chek::in_range!(3, ..=3);
+/// chek::in_range!(3.5, 1.0.., "With a message");
+/// chek::in_range!(3, .., "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if !outcome.is_within() {
+            $crate::__range_assert_fail!("in_range", value, range, stringify!($value), stringify!($range), outcome);
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if !outcome.is_within() {
+            $crate::__range_assert_fail!("in_range", value, range, stringify!($value), stringify!($range), outcome, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Panics if the first expression is within the range given by the second.
+///
+/// The range can be anything implementing `RangeBounds`, e.g. `..`, `a..`,
+/// `..=b`, `a..b` or `a..=b`.
+///
+/// Requires that the value implements `PartialOrd` and `Debug`, and that the
+/// range implements `Debug`.
+///
+/// A debug-only version is available as `chek::debug_not_in_range!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::not_in_range!(4, 0..4);
+/// chek::not_in_range!(4, ..=3);
+/// chek::not_in_range!(0.5, 1.0.., "With a message");
+/// chek::not_in_range!(5, 1..=4, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! not_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if outcome.is_within() {
+            $crate::__range_assert_fail!("not_in_range", value, range, stringify!($value), stringify!($range), outcome);
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if outcome.is_within() {
+            $crate::__range_assert_fail!("not_in_range", value, range, stringify!($value), stringify!($range), outcome, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::in_range!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// chek::debug_in_range!(3, 0..4);
+/// chek::debug_in_range!(3, 0..4, "With a message");
+/// chek::debug_in_range!(3, 0..4, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (value, range) = (&($value), &($range));
+            let outcome = $crate::__internal::range_outcome(value, range);
+            if !outcome.is_within() {
+                $crate::__range_assert_fail!("debug_in_range", value, range, stringify!($value), stringify!($range), outcome);
+            }
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (value, range) = (&($value), &($range));
+            let outcome = $crate::__internal::range_outcome(value, range);
+            if !outcome.is_within() {
+                $crate::__range_assert_fail!("debug_in_range", value, range, stringify!($value), stringify!($range), outcome, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Same as `chek::not_in_range!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// chek::debug_not_in_range!(4, 0..4);
+/// chek::debug_not_in_range!(4, 0..4, "With a message");
+/// chek::debug_not_in_range!(4, 0..4, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_not_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (value, range) = (&($value), &($range));
+            let outcome = $crate::__internal::range_outcome(value, range);
+            if outcome.is_within() {
+                $crate::__range_assert_fail!("debug_not_in_range", value, range, stringify!($value), stringify!($range), outcome);
+            }
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (value, range) = (&($value), &($range));
+            let outcome = $crate::__internal::range_outcome(value, range);
+            if outcome.is_within() {
+                $crate::__range_assert_fail!("debug_not_in_range", value, range, stringify!($value), stringify!($range), outcome, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the first expression is not within the range
+/// given by the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::in_range!`. Requires that the
+/// value implements `PartialOrd` and `Debug`, and that the range implements
+/// `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_in_range!(3, 0..4).is_ok());
+/// assert!(chek::try_in_range!(4, 0..4).is_err());
+/// assert!(chek::try_in_range!(4, 0..4, "With a message").is_err());
+/// assert!(chek::try_in_range!(4, 0..4, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if !outcome.is_within() {
+            Err($crate::__internal::range_failure("in_range", value, range, stringify!($value), stringify!($range), outcome, None))
+        } else {
+            Ok(())
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if !outcome.is_within() {
+            Err($crate::__internal::range_failure("in_range", value, range, stringify!($value), stringify!($range), outcome, Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the first expression is within the range
+/// given by the second, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::not_in_range!`. Requires that
+/// the value implements `PartialOrd` and `Debug`, and that the range implements
+/// `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_not_in_range!(4, 0..4).is_ok());
+/// assert!(chek::try_not_in_range!(3, 0..4).is_err());
+/// assert!(chek::try_not_in_range!(3, 0..4, "With a message").is_err());
+/// assert!(chek::try_not_in_range!(3, 0..4, "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_not_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if outcome.is_within() {
+            Err($crate::__internal::range_failure("not_in_range", value, range, stringify!($value), stringify!($range), outcome, None))
+        } else {
+            Ok(())
+        }
+    });
+    ($value:expr, $range:expr, $($msg_args:tt)+) => ({
+        let (value, range) = (&($value), &($range));
+        let outcome = $crate::__internal::range_outcome(value, range);
+        if outcome.is_within() {
+            Err($crate::__internal::range_failure("not_in_range", value, range, stringify!($value), stringify!($range), outcome, Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is not within the range given by the second.
+///
+/// This is the `Result`-returning version of `chek::in_range!`. Requires that
+/// the value implements `PartialOrd` and `Debug`, and that the range implements
+/// `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_in_range!(3, 0..4);
+///     chek::ensure_in_range!(4, 0..4);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_in_range!(4, 0..4, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_in_range!($value, $range) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $range:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_in_range!($value, $range) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the first
+/// expression is within the range given by the second.
+///
+/// This is the `Result`-returning version of `chek::not_in_range!`. Requires
+/// that the value implements `PartialOrd` and `Debug`, and that the range
+/// implements `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_not_in_range!(4, 0..4);
+///     chek::ensure_not_in_range!(3, 0..4);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_not_in_range!(3, 0..4, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_not_in_range {
+    ($value:expr, $range:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_in_range!($value, $range) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $range:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_not_in_range!($value, $range) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
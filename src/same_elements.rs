@@ -0,0 +1,171 @@
+/// Panics unless the two slices have the same elements, the same number of
+/// times each, in any order.
+///
+/// Takes anything that implements `AsRef<[T]>`, e.g. arrays, slices and
+/// `Vec`s, and requires that the elements implement `PartialEq` and `Debug`.
+/// If the elements are also `Hash + Eq` (and the `std` feature is enabled),
+/// they're counted with a `HashMap`, and if they're `Ord` (and the `alloc`
+/// feature is enabled), by sorting them. Otherwise, each element is counted by
+/// scanning both slices, which takes O(n²) time, but doesn't allocate.
+///
+/// On failure, shows the start of each slice, and then lists the elements of
+/// `right` that are missing from `left`, the extra elements in `left` that
+/// aren't in `right`, and the elements that are in both, but a different
+/// number of times.
+///
+/// A debug-only version is available as `chek::debug_same_elements!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let results = vec![3, 1, 2, 1];
+/// chek::same_elements!(results, [1, 1, 2, 3]);
+/// chek::same_elements!(&results[..2], [1, 3], "With a message");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = ([1, 2, 2, 4], [2, 1, 3, 2, 2]);
+/// // assertion failed: `chek::same_elements!(left, right)`
+/// //   left: `[1, 2, 2, 4]` = `x`,
+/// //  right: `[2, 1, 3, 2, 2]` = `y`
+/// // missing: `3`
+/// //   extra: `4`
+/// //  counts: `2` (left 2, right 3)
+/// chek::same_elements!(x, y);
+/// ```
+#[macro_export]
+macro_rules! same_elements {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(diff) = $crate::__same_elements!(left, right) {
+            $crate::__details_assert_fail!("same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                         stringify!($left), stringify!($right), &diff);
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(diff) = $crate::__same_elements!(left, right) {
+            $crate::__details_assert_fail!("same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                         stringify!($left), stringify!($right), &diff, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::same_elements!` in debug builds or release builds where the
+/// `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let results = vec![3, 1, 2, 1];
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_same_elements!(results, [1, 1, 2, 3]);
+/// chek::debug_same_elements!(&results[..2], [1, 3], "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_same_elements {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(diff) = $crate::__same_elements!(left, right) {
+                $crate::__details_assert_fail!("debug_same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                             stringify!($left), stringify!($right), &diff);
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(diff) = $crate::__same_elements!(left, right) {
+                $crate::__details_assert_fail!("debug_same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                             stringify!($left), stringify!($right), &diff, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` unless the two slices have the same elements,
+/// the same number of times each, in any order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::same_elements!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_same_elements!([3, 1, 2], [1, 2, 3]).is_ok());
+/// assert!(chek::try_same_elements!([1, 1, 2], [1, 2, 2], "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_same_elements {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__same_elements!(left, right) {
+            Some(diff) => Err($crate::__internal::details_failure("same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                                                stringify!($left), stringify!($right), &diff, None)),
+            None => Ok(()),
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__same_elements!(left, right) {
+            Some(diff) => Err($crate::__internal::details_failure("same_elements", &$crate::__internal::Preview(left), &$crate::__internal::Preview(right),
+                                                                stringify!($left), stringify!($right), &diff, Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function unless the two
+/// slices have the same elements, the same number of times each, in any order.
+///
+/// This is the `Result`-returning version of `chek::same_elements!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_same_elements!([3, 1, 2], [1, 2, 3]);
+///     chek::ensure_same_elements!([1, 1, 2], [1, 2, 2]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_same_elements!([1, 2], [1, 2, 3], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_same_elements {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_same_elements!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_same_elements!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,177 @@
+
+/// Panics if the two slices are not equal, reporting where they first differ.
+///
+/// Accepts anything implementing `AsRef<[T]>`, e.g. `&[T]`, `[T; N]` or
+/// `Vec<T>`. Rather than printing both slices in full, the failure message
+/// gives the index of the first differing element (or says that one is a
+/// prefix of the other), along with their lengths, and prints only a few
+/// elements on either side of the difference.
+///
+/// Requires that the elements implement `PartialEq` and `Debug`.
+///
+/// A debug-only version is available as `chek::debug_slice_eq!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 2, 3];
+/// chek::slice_eq!(v, [1, 2, 3]);
+/// chek::slice_eq!(&v[1..], &[2, 3], "With a message");
+/// chek::slice_eq!(v, vec![1, 2, 3], "With a formatted message: {}", "oh no");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (a, b) = ([0u8; 4096], [0u8; 4096]);
+/// let mut b = b;
+/// b[1000] = 1;
+/// // assertion failed: `chek::slice_eq!(left, right)`
+/// //   left: `[..., 0, 0, 0, 0, 0, 0, 0, ...]` = `a`,
+/// //  right: `[..., 0, 0, 0, 1, 0, 0, 0, ...]` = `b`
+/// // result: first difference at index 1000 (both have length 4096)
+/// chek::slice_eq!(a, b);
+/// ```
+#[macro_export]
+macro_rules! slice_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::slice_mismatch(left, right) {
+            let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+            $crate::__slice_assert_fail!("slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch);
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        if let Some(mismatch) = $crate::__internal::slice_mismatch(left, right) {
+            let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+            $crate::__slice_assert_fail!("slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::slice_eq!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let v = vec![1, 2, 3];
+/// chek::debug_slice_eq!(v, [1, 2, 3]);
+/// chek::debug_slice_eq!(v, [1, 2, 3], "With a message");
+/// chek::debug_slice_eq!(v, [1, 2, 3], "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_slice_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::slice_mismatch(left, right) {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+                $crate::__slice_assert_fail!("debug_slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch);
+            }
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right) = (&($left), &($right));
+            if let Some(mismatch) = $crate::__internal::slice_mismatch(left, right) {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+                $crate::__slice_assert_fail!("debug_slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two slices are not equal, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::slice_eq!`. Requires that the
+/// elements implement `PartialEq` and `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_slice_eq!([1, 2, 3], vec![1, 2, 3]).is_ok());
+/// assert!(chek::try_slice_eq!([1, 2, 3], vec![1, 2]).is_err());
+/// assert!(chek::try_slice_eq!([1, 2, 3], vec![1, 2], "With a message").is_err());
+/// assert!(chek::try_slice_eq!([1, 2, 3], vec![1, 2], "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_slice_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__internal::slice_mismatch(left, right) {
+            Some(mismatch) => {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+                Err($crate::__internal::slice_failure("slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch, None))
+            }
+            None => Ok(()),
+        }
+    });
+    ($left:expr, $right:expr, $($msg_args:tt)+) => ({
+        let (left, right) = (&($left), &($right));
+        match $crate::__internal::slice_mismatch(left, right) {
+            Some(mismatch) => {
+                let (left, right) = ($crate::__internal::slice_excerpt(left, mismatch), $crate::__internal::slice_excerpt(right, mismatch));
+                Err($crate::__internal::slice_failure("slice_eq", &left, &right, stringify!($left), stringify!($right), mismatch, Some(format_args!($($msg_args)+))))
+            }
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two slices
+/// are not equal.
+///
+/// This is the `Result`-returning version of `chek::slice_eq!`. Requires that
+/// the elements implement `PartialEq` and `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_slice_eq!([1, 2], vec![1, 2]);
+///     chek::ensure_slice_eq!([1, 2], vec![1, 3]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_slice_eq!([1, 2], vec![1, 3], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_slice_eq {
+    ($left:expr, $right:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_slice_eq!($left, $right) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_slice_eq!($left, $right) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
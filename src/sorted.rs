@@ -0,0 +1,607 @@
+
+/// Panics if the items of the provided collection or iterator are not sorted in
+/// ascending order, i.e. if any item is greater than the one after it.
+///
+/// Accepts anything implementing `IntoIterator`, e.g. `&[T]`, `&Vec<T>`, or an
+/// iterator. Doesn't allocate. On failure, reports the first pair of neighbors
+/// that are out of order, and their indices.
+///
+/// Requires that the items implement `PartialOrd` and `Debug`. Note that an
+/// item which is incomparable to its neighbor (e.g. NaN) counts as out of
+/// order.
+///
+/// A debug-only version is available as `chek::debug_sorted!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 3, 5];
+/// chek::sorted!(&[1, 2, 2, 3]);
+/// chek::sorted!(v.iter().map(|x| x * 2), "With a message");
+/// chek::sorted!(v.iter().map(|x| x * 2), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! sorted {
+    ($items:expr $(,)?) => ({
+        if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+            $crate::__sorted_fail!("sorted", stringify!($items), index, &left, &right);
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+            $crate::__sorted_fail!("sorted", stringify!($items), index, &left, &right, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Panics if the items of the provided collection or iterator are not sorted in
+/// strictly ascending order, i.e. if any item is greater than or equal to the
+/// one after it.
+///
+/// Accepts anything implementing `IntoIterator`, e.g. `&[T]`, `&Vec<T>`, or an
+/// iterator. Doesn't allocate. On failure, reports the first pair of neighbors
+/// that are out of order, and their indices.
+///
+/// Requires that the items implement `PartialOrd` and `Debug`.
+///
+/// A debug-only version is available as `chek::debug_strictly_sorted!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 3, 5];
+/// chek::strictly_sorted!(&[1, 2, 3]);
+/// chek::strictly_sorted!(v.iter().map(|x| x * 2), "With a message");
+/// chek::strictly_sorted!(v.iter().map(|x| x * 2), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! strictly_sorted {
+    ($items:expr $(,)?) => ({
+        if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+            $crate::__sorted_fail!("strictly_sorted", stringify!($items), index, &left, &right);
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+            $crate::__sorted_fail!("strictly_sorted", stringify!($items), index, &left, &right, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Panics if the items of the provided collection or iterator are not sorted
+/// according to the provided comparison function.
+///
+/// The comparison function takes two items by reference and returns an
+/// `Ordering`, as with `slice::sort_by`. A pair of neighbors is out of order if
+/// it returns `Ordering::Greater` for them. Items that are already references,
+/// e.g. those of `&[T]`, are passed as they are, so for a `&[T]` it takes
+/// `&T`, just like `slice::sort_by` would.
+///
+/// Accepts anything implementing `IntoIterator`, e.g. `&[T]`, `&Vec<T>`, or an
+/// iterator. Doesn't allocate. On failure, reports the first pair of neighbors
+/// that are out of order, and their indices.
+///
+/// Requires that the items implement `Debug`.
+///
+/// A debug-only version is available as `chek::debug_sorted_by!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 3, 5];
+/// chek::sorted_by!(&[3, 2, 2, 1], |a, b| b.cmp(a));
+/// chek::sorted_by!(&[0.5, 1.0, f64::NAN], f64::total_cmp);
+/// chek::sorted_by!(&v, |a, b| a.cmp(b), "With a message");
+/// chek::sorted_by!(&v, |a, b| a.cmp(b), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! sorted_by {
+    ($items:expr, $compare:expr $(,)?) => ({
+        if let Some((index, left, right)) = $crate::__unsorted_pair!(by, $items, $compare) {
+            $crate::__sorted_fail!("sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right);
+        }
+    });
+    ($items:expr, $compare:expr, $($msg_args:tt)+) => ({
+        if let Some((index, left, right)) = $crate::__unsorted_pair!(by, $items, $compare) {
+            $crate::__sorted_fail!("sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Panics if the items of the provided collection or iterator are not sorted in
+/// ascending order of the key returned by the provided function.
+///
+/// The key function takes an item by reference, as with `slice::sort_by_key`,
+/// and may return any type implementing `PartialOrd`. Items that are already
+/// references, e.g. those of `&[T]`, are passed as they are, so for a `&[T]` it
+/// takes `&T`, just like `slice::sort_by_key` would.
+///
+/// Accepts anything implementing `IntoIterator`, e.g. `&[T]`, `&Vec<T>`, or an
+/// iterator. Doesn't allocate. On failure, reports the first pair of neighbors
+/// that are out of order, and their indices.
+///
+/// Requires that the items implement `Debug`.
+///
+/// A debug-only version is available as `chek::debug_sorted_by_key!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 3, 5];
+/// chek::sorted_by_key!(&[-1i32, 2, -3], |x| x.abs());
+/// chek::sorted_by_key!(&v, |&x| x / 2, "With a message");
+/// chek::sorted_by_key!(&v, |&x| x / 2, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! sorted_by_key {
+    ($items:expr, $key:expr $(,)?) => ({
+        if let Some((index, left, right)) = $crate::__unsorted_pair!(by_key, $items, $key) {
+            $crate::__sorted_fail!("sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right);
+        }
+    });
+    ($items:expr, $key:expr, $($msg_args:tt)+) => ({
+        if let Some((index, left, right)) = $crate::__unsorted_pair!(by_key, $items, $key) {
+            $crate::__sorted_fail!("sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right, format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::sorted!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let v = vec![1, 3, 5];
+/// chek::debug_sorted!(&[1, 2, 2, 3]);
+/// chek::debug_sorted!(v.iter().map(|x| x * 2), "With a message");
+/// chek::debug_sorted!(v.iter().map(|x| x * 2), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_sorted {
+    ($items:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+                $crate::__sorted_fail!("debug_sorted", stringify!($items), index, &left, &right);
+            }
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+                $crate::__sorted_fail!("debug_sorted", stringify!($items), index, &left, &right, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Same as `chek::strictly_sorted!` in debug builds or release builds where
+/// the `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let v = vec![1, 3, 5];
+/// chek::debug_strictly_sorted!(&[1, 2, 3]);
+/// chek::debug_strictly_sorted!(v.iter().map(|x| x * 2), "With a message");
+/// chek::debug_strictly_sorted!(v.iter().map(|x| x * 2), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_strictly_sorted {
+    ($items:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+                $crate::__sorted_fail!("debug_strictly_sorted", stringify!($items), index, &left, &right);
+            }
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+                $crate::__sorted_fail!("debug_strictly_sorted", stringify!($items), index, &left, &right, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Same as `chek::sorted_by!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let v = vec![1, 3, 5];
+/// chek::debug_sorted_by!(&[3, 2, 2, 1], |a, b| b.cmp(a));
+/// chek::debug_sorted_by!(&v, |a, b| a.cmp(b), "With a message");
+/// chek::debug_sorted_by!(&v, |a, b| a.cmp(b), "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_sorted_by {
+    ($items:expr, $compare:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__unsorted_pair!(by, $items, $compare) {
+                $crate::__sorted_fail!("debug_sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right);
+            }
+        }
+    });
+    ($items:expr, $compare:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__unsorted_pair!(by, $items, $compare) {
+                $crate::__sorted_fail!("debug_sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Same as `chek::sorted_by_key!` in debug builds or release builds where
+/// the `-C debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are no-ops if debug_assertions are off!
+/// let v = vec![1, 3, 5];
+/// chek::debug_sorted_by_key!(&[-1i32, 2, -3], |x| x.abs());
+/// chek::debug_sorted_by_key!(&v, |&x| x / 2, "With a message");
+/// chek::debug_sorted_by_key!(&v, |&x| x / 2, "With a formatted message: {}", "oh no");
+/// ```
+#[macro_export]
+macro_rules! debug_sorted_by_key {
+    ($items:expr, $key:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__unsorted_pair!(by_key, $items, $key) {
+                $crate::__sorted_fail!("debug_sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right);
+            }
+        }
+    });
+    ($items:expr, $key:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            if let Some((index, left, right)) = $crate::__unsorted_pair!(by_key, $items, $key) {
+                $crate::__sorted_fail!("debug_sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right, format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the items of the provided collection or
+/// iterator are not sorted in ascending order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::sorted!`. Requires that the
+/// items implement `PartialOrd` and `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_sorted!(&[1, 2, 2, 3]).is_ok());
+/// assert!(chek::try_sorted!(&[1, 3, 2]).is_err());
+/// assert!(chek::try_sorted!(&[1, 3, 2], "With a message").is_err());
+/// assert!(chek::try_sorted!(&[1, 3, 2], "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_sorted {
+    ($items:expr $(,)?) => ({
+        match $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted", stringify!($items), index, &left, &right, None)),
+            None => Ok(()),
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        match $crate::__internal::unsorted_pair($items, |a, b| a <= b) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted", stringify!($items), index, &left, &right, Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the items of the provided collection or
+/// iterator are not sorted in strictly ascending order, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::strictly_sorted!`. Requires that
+/// the items implement `PartialOrd` and `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_strictly_sorted!(&[1, 2, 3]).is_ok());
+/// assert!(chek::try_strictly_sorted!(&[1, 2, 2]).is_err());
+/// assert!(chek::try_strictly_sorted!(&[1, 2, 2], "With a message").is_err());
+/// assert!(chek::try_strictly_sorted!(&[1, 2, 2], "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_strictly_sorted {
+    ($items:expr $(,)?) => ({
+        match $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("strictly_sorted", stringify!($items), index, &left, &right, None)),
+            None => Ok(()),
+        }
+    });
+    ($items:expr, $($msg_args:tt)+) => ({
+        match $crate::__internal::unsorted_pair($items, |a, b| a < b) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("strictly_sorted", stringify!($items), index, &left, &right, Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the items of the provided collection or
+/// iterator are not sorted according to the provided comparison function, and
+/// `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::sorted_by!`. Requires that the
+/// items implement `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_sorted_by!(&[3, 2, 2, 1], |a, b| b.cmp(a)).is_ok());
+/// assert!(chek::try_sorted_by!(&[1, 2, 3], |a, b| b.cmp(a)).is_err());
+/// assert!(chek::try_sorted_by!(&[1, 2, 3], |a, b| b.cmp(a), "With a message").is_err());
+/// assert!(chek::try_sorted_by!(&[1, 2, 3], |a, b| b.cmp(a), "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_sorted_by {
+    ($items:expr, $compare:expr $(,)?) => ({
+        match $crate::__unsorted_pair!(by, $items, $compare) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right, None)),
+            None => Ok(()),
+        }
+    });
+    ($items:expr, $compare:expr, $($msg_args:tt)+) => ({
+        match $crate::__unsorted_pair!(by, $items, $compare) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted_by", concat!(stringify!($items), ", ", stringify!($compare)), index, &left, &right, Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the items of the provided collection or
+/// iterator are not sorted in ascending order of the key returned by the
+/// provided function, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::sorted_by_key!`. Requires that
+/// the items implement `Debug`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_sorted_by_key!(&[-1i32, 2, -3], |x| x.abs()).is_ok());
+/// assert!(chek::try_sorted_by_key!(&[-1i32, 3, -2], |x| x.abs()).is_err());
+/// assert!(chek::try_sorted_by_key!(&[-1i32, 3, -2], |x| x.abs(), "With a message").is_err());
+/// assert!(chek::try_sorted_by_key!(&[-1i32, 3, -2], |x| x.abs(), "With a formatted message: {}", "oh no").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_sorted_by_key {
+    ($items:expr, $key:expr $(,)?) => ({
+        match $crate::__unsorted_pair!(by_key, $items, $key) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right, None)),
+            None => Ok(()),
+        }
+    });
+    ($items:expr, $key:expr, $($msg_args:tt)+) => ({
+        match $crate::__unsorted_pair!(by_key, $items, $key) {
+            Some((index, left, right)) => Err($crate::__internal::sorted_failure("sorted_by_key", concat!(stringify!($items), ", ", stringify!($key)), index, &left, &right, Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the items of the
+/// provided collection or iterator are not sorted in ascending order.
+///
+/// This is the `Result`-returning version of `chek::sorted!`. Requires that the
+/// items implement `PartialOrd` and `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_sorted!(&[1, 2, 2, 3]);
+///     chek::ensure_sorted!(&[1, 3, 2]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_sorted!(&[1, 3, 2], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_sorted {
+    ($items:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted!($items) {
+            return Err(From::from(failure));
+        }
+    });
+    ($items:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted!($items) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the items of the
+/// provided collection or iterator are not sorted in strictly ascending order.
+///
+/// This is the `Result`-returning version of `chek::strictly_sorted!`. Requires
+/// that the items implement `PartialOrd` and `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_strictly_sorted!(&[1, 2, 3]);
+///     chek::ensure_strictly_sorted!(&[1, 2, 2]);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_strictly_sorted!(&[1, 2, 2], "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_strictly_sorted {
+    ($items:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_strictly_sorted!($items) {
+            return Err(From::from(failure));
+        }
+    });
+    ($items:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_strictly_sorted!($items) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the items of the
+/// provided collection or iterator are not sorted according to the provided
+/// comparison function.
+///
+/// This is the `Result`-returning version of `chek::sorted_by!`. Requires that
+/// the items implement `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_sorted_by!(&[3, 2, 2, 1], |a, b| b.cmp(a));
+///     chek::ensure_sorted_by!(&[1, 2, 3], |a, b| b.cmp(a));
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_sorted_by!(&[1, 2, 3], |a, b| b.cmp(a), "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_sorted_by {
+    ($items:expr, $compare:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted_by!($items, $compare) {
+            return Err(From::from(failure));
+        }
+    });
+    ($items:expr, $compare:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted_by!($items, $compare) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the items of the
+/// provided collection or iterator are not sorted in ascending order of the key
+/// returned by the provided function.
+///
+/// This is the `Result`-returning version of `chek::sorted_by_key!`. Requires
+/// that the items implement `Debug`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_sorted_by_key!(&[-1i32, 2, -3], |x| x.abs());
+///     chek::ensure_sorted_by_key!(&[-1i32, 3, -2], |x| x.abs());
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_sorted_by_key!(&[-1i32, 3, -2], |x| x.abs(), "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_sorted_by_key {
+    ($items:expr, $key:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted_by_key!($items, $key) {
+            return Err(From::from(failure));
+        }
+    });
+    ($items:expr, $key:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_sorted_by_key!($items, $key) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
@@ -0,0 +1,476 @@
+
+/// Panics unless the absolute difference between the two floats is at most
+/// `max_diff`, i.e. unless `|left - right| <= max_diff`.
+///
+/// This is the right check for values near zero, where a relative tolerance
+/// would be too strict. Equal values (including equal infinities) always pass,
+/// and NaN always fails.
+///
+/// On failure, the message shows the absolute and relative differences and
+/// the ULP distance between the values, alongside the limit.
+///
+/// A debug-only version is available as `chek::debug_abs_diff_le!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::abs_diff_le!(0.1 + 0.2, 0.3, 1e-9);
+/// chek::abs_diff_le!(1e-12f32, 0.0, 1e-6, "should be tiny");
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let (x, y) = (1.0, 1.1);
+/// // assertion failed: `chek::abs_diff_le!(left, right)`
+/// //   left: `1.0` = `x`,
+/// //  right: `1.1` = `y`
+/// //   diff: `0.10000000000000009` (absolute), `0.09090909090909098` (relative), `450359962737050` (ulps)
+/// //  limit: `0.01` (absolute)
+/// chek::abs_diff_le!(x, y, 0.01);
+/// ```
+#[macro_export]
+macro_rules! abs_diff_le {
+    ($left:expr, $right:expr, $max_diff:expr $(,)?) => ({
+        let (left, right, max_diff) = ($left, $right, $max_diff);
+        if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+            $crate::__details_assert_fail!("abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "absolute", max_diff));
+        }
+    });
+    ($left:expr, $right:expr, $max_diff:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_diff) = ($left, $right, $max_diff);
+        if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+            $crate::__details_assert_fail!("abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "absolute", max_diff), format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::abs_diff_le!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_abs_diff_le!(0.1 + 0.2, 0.3, 1e-9);
+/// chek::debug_abs_diff_le!(1e-12f32, 0.0, 1e-6, "should be tiny");
+/// ```
+#[macro_export]
+macro_rules! debug_abs_diff_le {
+    ($left:expr, $right:expr, $max_diff:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_diff) = ($left, $right, $max_diff);
+            if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+                $crate::__details_assert_fail!("debug_abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "absolute", max_diff));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $max_diff:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_diff) = ($left, $right, $max_diff);
+            if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+                $crate::__details_assert_fail!("debug_abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "absolute", max_diff), format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two floats are further apart than
+/// `max_diff` allows, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::abs_diff_le!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_abs_diff_le!(0.1 + 0.2, 0.3, 1e-9).is_ok());
+/// assert!(chek::try_abs_diff_le!(1.0, 1.0 + 1e-6, 1e-9, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_abs_diff_le {
+    ($left:expr, $right:expr, $max_diff:expr $(,)?) => ({
+        let (left, right, max_diff) = ($left, $right, $max_diff);
+        if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+            Err($crate::__internal::details_failure("abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "absolute", max_diff), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $max_diff:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_diff) = ($left, $right, $max_diff);
+        if !$crate::__internal::abs_diff_le(left, right, max_diff) {
+            Err($crate::__internal::details_failure("abs_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "absolute", max_diff), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two floats
+/// are further apart than `max_diff` allows.
+///
+/// This is the `Result`-returning version of `chek::abs_diff_le!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_abs_diff_le!(0.1 + 0.2, 0.3, 1e-9);
+///     chek::ensure_abs_diff_le!(1.0, 1.0 + 1e-6, 1e-9);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_abs_diff_le!(1.0, 1.0 + 1e-6, 1e-9, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_abs_diff_le {
+    ($left:expr, $right:expr, $max_diff:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_abs_diff_le!($left, $right, $max_diff) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $max_diff:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_abs_diff_le!($left, $right, $max_diff) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the relative difference between the two floats is at most
+/// `max_rel`, i.e. unless `|left - right| <= max_rel * max(|left|, |right|)`.
+///
+/// This scales with the magnitude of the values, so it isn't useful for values
+/// near zero, which should use `chek::abs_diff_le!` instead. Equal values
+/// (including equal infinities) always pass, and NaN always fails.
+///
+/// On failure, the message shows the absolute and relative differences and
+/// the ULP distance between the values, alongside the limit.
+///
+/// A debug-only version is available as `chek::debug_rel_diff_le!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::rel_diff_le!(1.0e6, 1.000001e6, 1e-6);
+/// chek::rel_diff_le!(100.0f32, 101.0, 0.01, "within a percent");
+/// ```
+#[macro_export]
+macro_rules! rel_diff_le {
+    ($left:expr, $right:expr, $max_rel:expr $(,)?) => ({
+        let (left, right, max_rel) = ($left, $right, $max_rel);
+        if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+            $crate::__details_assert_fail!("rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "relative", max_rel));
+        }
+    });
+    ($left:expr, $right:expr, $max_rel:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_rel) = ($left, $right, $max_rel);
+        if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+            $crate::__details_assert_fail!("rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "relative", max_rel), format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::rel_diff_le!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_rel_diff_le!(1.0e6, 1.000001e6, 1e-6);
+/// chek::debug_rel_diff_le!(100.0f32, 101.0, 0.01, "within a percent");
+/// ```
+#[macro_export]
+macro_rules! debug_rel_diff_le {
+    ($left:expr, $right:expr, $max_rel:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_rel) = ($left, $right, $max_rel);
+            if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+                $crate::__details_assert_fail!("debug_rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "relative", max_rel));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $max_rel:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_rel) = ($left, $right, $max_rel);
+            if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+                $crate::__details_assert_fail!("debug_rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "relative", max_rel), format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two floats are further apart than
+/// `max_rel` allows, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::rel_diff_le!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_rel_diff_le!(100.0, 101.0, 0.01).is_ok());
+/// assert!(chek::try_rel_diff_le!(100.0, 102.0, 0.01, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_rel_diff_le {
+    ($left:expr, $right:expr, $max_rel:expr $(,)?) => ({
+        let (left, right, max_rel) = ($left, $right, $max_rel);
+        if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+            Err($crate::__internal::details_failure("rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "relative", max_rel), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $max_rel:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_rel) = ($left, $right, $max_rel);
+        if !$crate::__internal::rel_diff_le(left, right, max_rel) {
+            Err($crate::__internal::details_failure("rel_diff_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "relative", max_rel), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two floats
+/// are further apart than `max_rel` allows.
+///
+/// This is the `Result`-returning version of `chek::rel_diff_le!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_rel_diff_le!(100.0, 101.0, 0.01);
+///     chek::ensure_rel_diff_le!(100.0, 102.0, 0.01);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_rel_diff_le!(100.0, 102.0, 0.01, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_rel_diff_le {
+    ($left:expr, $right:expr, $max_rel:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_rel_diff_le!($left, $right, $max_rel) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $max_rel:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_rel_diff_le!($left, $right, $max_rel) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Panics unless the two floats are at most `max_ulps` representable values
+/// apart, i.e. unless there are fewer than `max_ulps` floats strictly between
+/// them.
+///
+/// `max_ulps` is a `u64`. `-0.0` and `0.0` count as the same value, and NaN
+/// always fails. Like a relative tolerance, this isn't useful for values near
+/// zero, since there are as many floats between `0.0` and `1e-30` as between
+/// `1e-30` and `1.0`.
+///
+/// On failure, the message shows the absolute and relative differences and
+/// the ULP distance between the values, alongside the limit.
+///
+/// A debug-only version is available as `chek::debug_ulps_le!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// chek::ulps_le!(0.1 + 0.2, 0.3, 1);
+/// chek::ulps_le!(1.0f32, 1.0 + f32::EPSILON, 1, "one ulp apart");
+/// ```
+#[macro_export]
+macro_rules! ulps_le {
+    ($left:expr, $right:expr, $max_ulps:expr $(,)?) => ({
+        let (left, right, max_ulps) = ($left, $right, $max_ulps);
+        if !$crate::__internal::ulps_le(left, right, max_ulps) {
+            $crate::__details_assert_fail!("ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "ulps", max_ulps));
+        }
+    });
+    ($left:expr, $right:expr, $max_ulps:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_ulps) = ($left, $right, $max_ulps);
+        if !$crate::__internal::ulps_le(left, right, max_ulps) {
+            $crate::__details_assert_fail!("ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                         &$crate::__internal::float_diff(left, right, "ulps", max_ulps), format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::ulps_le!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_ulps_le!(0.1 + 0.2, 0.3, 1);
+/// chek::debug_ulps_le!(1.0f32, 1.0 + f32::EPSILON, 1, "one ulp apart");
+/// ```
+#[macro_export]
+macro_rules! debug_ulps_le {
+    ($left:expr, $right:expr, $max_ulps:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_ulps) = ($left, $right, $max_ulps);
+            if !$crate::__internal::ulps_le(left, right, max_ulps) {
+                $crate::__details_assert_fail!("debug_ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "ulps", max_ulps));
+            }
+        }
+    });
+    ($left:expr, $right:expr, $max_ulps:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            let (left, right, max_ulps) = ($left, $right, $max_ulps);
+            if !$crate::__internal::ulps_le(left, right, max_ulps) {
+                $crate::__details_assert_fail!("debug_ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                             &$crate::__internal::float_diff(left, right, "ulps", max_ulps), format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if the two floats are further apart than
+/// `max_ulps` allows, and `Ok(())` otherwise.
+///
+/// This is the non-panicking version of `chek::ulps_le!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_ulps_le!(0.1 + 0.2, 0.3, 1).is_ok());
+/// assert!(chek::try_ulps_le!(1.0, 1.0 + 4.0 * f64::EPSILON, 2, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_ulps_le {
+    ($left:expr, $right:expr, $max_ulps:expr $(,)?) => ({
+        let (left, right, max_ulps) = ($left, $right, $max_ulps);
+        if !$crate::__internal::ulps_le(left, right, max_ulps) {
+            Err($crate::__internal::details_failure("ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "ulps", max_ulps), None))
+        } else {
+            Ok(())
+        }
+    });
+    ($left:expr, $right:expr, $max_ulps:expr, $($msg_args:tt)+) => ({
+        let (left, right, max_ulps) = ($left, $right, $max_ulps);
+        if !$crate::__internal::ulps_le(left, right, max_ulps) {
+            Err($crate::__internal::details_failure("ulps_le", &left, &right, stringify!($left), stringify!($right),
+                                                  &$crate::__internal::float_diff(left, right, "ulps", max_ulps), Some(format_args!($($msg_args)+))))
+        } else {
+            Ok(())
+        }
+    });
+}
+
+/// Returns early with an error from the enclosing function if the two floats
+/// are further apart than `max_ulps` allows.
+///
+/// This is the `Result`-returning version of `chek::ulps_le!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_ulps_le!(0.1 + 0.2, 0.3, 1);
+///     chek::ensure_ulps_le!(1.0, 1.0 + 4.0 * f64::EPSILON, 2);
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_ulps_le!(1.0, 1.0 + 4.0 * f64::EPSILON, 2, "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_ulps_le {
+    ($left:expr, $right:expr, $max_ulps:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_ulps_le!($left, $right, $max_ulps) {
+            return Err(From::from(failure));
+        }
+    });
+    ($left:expr, $right:expr, $max_ulps:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_ulps_le!($left, $right, $max_ulps) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
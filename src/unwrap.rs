@@ -0,0 +1,755 @@
+
+/// Returns the contents of an `Option`, panicking if it's `None`.
+///
+/// Like `Option::unwrap`, but the failure message includes the expression that
+/// produced the `None`, and the optional message. The contents don't need to
+/// implement `Debug`.
+///
+/// If a failure handler is installed (see `chek::set_failure_handler`) and it
+/// returns, this panics anyway, since there's no value to return.
+///
+/// A debug-only version is available as `chek::debug_some!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let mut map = std::collections::HashMap::new();
+/// map.insert("a", 1);
+/// let a = chek::some!(map.get("a"));
+/// let b = chek::some!(map.get("a").copied(), "With a message");
+/// assert_eq!(*a + b, 2);
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let map = std::collections::HashMap::<&str, i32>::new();
+/// // assertion failed: `chek::some!(value)`: no entry for b
+/// //  value: `None` = `map.get("b")`
+/// chek::some!(map.get("b"), "no entry for {}", "b");
+/// ```
+#[macro_export]
+macro_rules! some {
+    ($value:expr $(,)?) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                $crate::__value_assert_fail!("some", &None::<()>, stringify!($value));
+                $crate::__internal::no_value("some")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                $crate::__value_assert_fail!("some", &None::<()>, stringify!($value), format_args!($($msg_args)+));
+                $crate::__internal::no_value("some")
+            }
+        }
+    );
+}
+
+/// Same as `chek::some!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, a
+/// `None` still panics, but without the message, which is never formatted.
+///
+/// To skip the check entirely in release builds, see
+/// `chek::debug_some_unchecked!`.
+///
+/// # Example
+///
+/// ```rust
+/// let value = Some(10);
+/// let a = chek::debug_some!(value);
+/// let b = chek::debug_some!(value, "With a message");
+/// assert_eq!(a + b, 20);
+/// ```
+#[macro_export]
+macro_rules! debug_some {
+    ($value:expr $(,)?) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_some", &None::<()>, stringify!($value));
+                }
+                $crate::__internal::no_value("debug_some")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_some", &None::<()>, stringify!($value), format_args!($($msg_args)+));
+                }
+                $crate::__internal::no_value("debug_some")
+            }
+        }
+    );
+}
+
+/// Same as `chek::some!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, a
+/// `None` is a `std::hint::unreachable_unchecked()` call. This is `unsafe` to
+/// call in both debug and release builds.
+///
+/// # Example
+///
+/// ```rust
+/// let value = Some(10);
+/// // Obviously, be extremely sure you're correct if you use this.
+/// let contents = unsafe { chek::debug_some_unchecked!(value, "optional message") };
+/// assert_eq!(contents, 10);
+/// ```
+#[macro_export]
+macro_rules! debug_some_unchecked {
+    ($value:expr $(,)?) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_some_unchecked", &None::<()>, stringify!($value));
+                    $crate::__internal::no_value("debug_some_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Some(value) => value,
+            None => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_some_unchecked", &None::<()>, stringify!($value), format_args!($($msg_args)+));
+                    $crate::__internal::no_value("debug_some_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+}
+
+/// Returns `Ok` with the contents of an `Option`, or `Err(chek::Failure)` if
+/// it's `None`.
+///
+/// This is the non-panicking version of `chek::some!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(chek::try_some!(Some(3)).ok(), Some(3));
+/// assert!(chek::try_some!(None::<i32>, "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_some {
+    ($value:expr $(,)?) => (
+        match $value {
+            Some(value) => Ok(value),
+            None => Err($crate::__internal::value_failure("some", &None::<()>, stringify!($value), None)),
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Some(value) => Ok(value),
+            None => Err($crate::__internal::value_failure("some", &None::<()>, stringify!($value), Some(format_args!($($msg_args)+)))),
+        }
+    );
+}
+
+/// Returns the contents of an `Option`, or returns early with an error from
+/// the enclosing function if it's `None`.
+///
+/// This is the `Result`-returning version of `chek::some!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(value: Option<i32>) -> Result<i32, chek::Failure> {
+///     Ok(chek::ensure_some!(value) + 1)
+/// }
+///
+/// fn with_error(value: Option<i32>) -> Result<i32, &'static str> {
+///     Ok(chek::ensure_some!(value, "With an error") + 1)
+/// }
+///
+/// assert_eq!(no_error(Some(1)).ok(), Some(2));
+/// assert!(no_error(None).is_err());
+/// assert_eq!(with_error(None), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_some {
+    ($value:expr $(,)?) => (
+        match $crate::try_some!($value) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from(failure)),
+        }
+    );
+    ($value:expr, $err:expr $(,)?) => (
+        match $crate::try_some!($value) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from($crate::__ensure_error!($err, failure))),
+        }
+    );
+}
+
+/// Panics if an `Option` is `Some`, showing what it contains.
+///
+/// If the contents don't implement `Debug`, they're shown as
+/// `<type name: Debug not available here>` on failure.
+///
+/// A debug-only version is available as `chek::debug_none!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let v = vec![1, 2, 3];
+/// chek::none!(v.get(3));
+/// chek::none!(v.iter().position(|&x| x > 3), "With a message");
+/// ```
+#[macro_export]
+macro_rules! none {
+    ($value:expr $(,)?) => ({
+        if let Some(value) = $value {
+            $crate::__value_assert_fail!("none", &Some($crate::__debug!(&value)), stringify!($value));
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if let Some(value) = $value {
+            $crate::__value_assert_fail!("none", &Some($crate::__debug!(&value)), stringify!($value), format_args!($($msg_args)+));
+        }
+    });
+}
+
+/// Same as `chek::none!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds,
+/// vanishes without a trace.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// // These are compiled to nothing if debug_assertions are off!
+/// chek::debug_none!(None::<i32>);
+/// chek::debug_none!("abc".find('d'), "With a message");
+/// ```
+#[macro_export]
+macro_rules! debug_none {
+    ($value:expr $(,)?) => ({
+        if cfg!(debug_assertions) {
+            if let Some(value) = $value {
+                $crate::__value_assert_fail!("debug_none", &Some($crate::__debug!(&value)), stringify!($value));
+            }
+        }
+    });
+    ($value:expr, $($msg_args:tt)+) => ({
+        if cfg!(debug_assertions) {
+            if let Some(value) = $value {
+                $crate::__value_assert_fail!("debug_none", &Some($crate::__debug!(&value)), stringify!($value), format_args!($($msg_args)+));
+            }
+        }
+    });
+}
+
+/// Returns `Err(chek::Failure)` if an `Option` is `Some`, and `Ok(())`
+/// otherwise.
+///
+/// This is the non-panicking version of `chek::none!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_none!(None::<i32>).is_ok());
+/// assert!(chek::try_none!(Some(3), "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_none {
+    ($value:expr $(,)?) => (
+        match $value {
+            Some(value) => Err($crate::__internal::value_failure("none", &Some($crate::__debug!(&value)), stringify!($value), None)),
+            None => Ok(()),
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Some(value) => Err($crate::__internal::value_failure("none", &Some($crate::__debug!(&value)), stringify!($value), Some(format_args!($($msg_args)+)))),
+            None => Ok(()),
+        }
+    );
+}
+
+/// Returns early with an error from the enclosing function if an `Option` is
+/// `Some`.
+///
+/// This is the `Result`-returning version of `chek::none!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error() -> Result<(), chek::Failure> {
+///     chek::ensure_none!(None::<i32>);
+///     chek::ensure_none!(Some(3));
+///     Ok(())
+/// }
+///
+/// fn with_error() -> Result<(), &'static str> {
+///     chek::ensure_none!(Some(3), "With an error");
+///     Ok(())
+/// }
+///
+/// assert!(no_error().is_err());
+/// assert_eq!(with_error(), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_none {
+    ($value:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_none!($value) {
+            return Err(From::from(failure));
+        }
+    });
+    ($value:expr, $err:expr $(,)?) => ({
+        if let Err(failure) = $crate::try_none!($value) {
+            return Err(From::from($crate::__ensure_error!($err, failure)));
+        }
+    });
+}
+
+/// Returns the contents of a `Result`, panicking if it's an `Err`.
+///
+/// Like `Result::unwrap`, but the failure message includes the expression that
+/// produced the error, and the optional message. Neither the error
+/// nor the `Ok` value need to implement `Debug`, although the error is shown as
+/// `<type name: Debug not available here>` on failure if it doesn't.
+///
+/// If a failure handler is installed (see `chek::set_failure_handler`) and it
+/// returns, this panics anyway, since there's no value to return.
+///
+/// A debug-only version is available as `chek::debug_ok!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let a: i32 = chek::ok!("1".parse());
+/// let b: i32 = chek::ok!("2".parse(), "With a message");
+/// assert_eq!(a + b, 3);
+/// ```
+///
+/// Fails with a message like:
+///
+/// ```rust,should_panic
+/// let s = "x";
+/// // assertion failed: `chek::ok!(value)`: bad input "x"
+/// //  value: `Err(ParseIntError { kind: InvalidDigit })` = `s.parse::<i32>()`
+/// chek::ok!(s.parse::<i32>(), "bad input {:?}", s);
+/// ```
+#[macro_export]
+macro_rules! ok {
+    ($value:expr $(,)?) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                $crate::__value_assert_fail!("ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value));
+                $crate::__internal::no_value("ok")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                $crate::__value_assert_fail!("ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value), format_args!($($msg_args)+));
+                $crate::__internal::no_value("ok")
+            }
+        }
+    );
+}
+
+/// Same as `chek::ok!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, an
+/// `Err` still panics, but without the error or the message, which are never
+/// formatted.
+///
+/// To skip the check entirely in release builds, see
+/// `chek::debug_ok_unchecked!`.
+///
+/// # Example
+///
+/// ```rust
+/// let a: i32 = chek::debug_ok!("1".parse());
+/// let b: i32 = chek::debug_ok!("2".parse(), "With a message");
+/// assert_eq!(a + b, 3);
+/// ```
+#[macro_export]
+macro_rules! debug_ok {
+    ($value:expr $(,)?) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value));
+                }
+                $crate::__internal::no_value("debug_ok")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value), format_args!($($msg_args)+));
+                }
+                $crate::__internal::no_value("debug_ok")
+            }
+        }
+    );
+}
+
+/// Same as `chek::ok!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, an
+/// `Err` is a `std::hint::unreachable_unchecked()` call. This is `unsafe` to
+/// call in both debug and release builds.
+///
+/// # Example
+///
+/// ```rust
+/// // Obviously, be extremely sure you're correct if you use this.
+/// let value: i32 = unsafe { chek::debug_ok_unchecked!("10".parse(), "optional message") };
+/// assert_eq!(value, 10);
+/// ```
+#[macro_export]
+macro_rules! debug_ok_unchecked {
+    ($value:expr $(,)?) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_ok_unchecked", &Err::<(), _>($crate::__debug!(&error)), stringify!($value));
+                    $crate::__internal::no_value("debug_ok_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Ok(value) => value,
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_ok_unchecked", &Err::<(), _>($crate::__debug!(&error)), stringify!($value), format_args!($($msg_args)+));
+                    $crate::__internal::no_value("debug_ok_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+}
+
+/// Returns `Ok` with the contents of a `Result`, or `Err(chek::Failure)` if
+/// it's an `Err`.
+///
+/// This is the non-panicking version of `chek::ok!`. Note that the original
+/// error is only kept in its formatted form, in the `Failure`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(chek::try_ok!("3".parse::<i32>()).ok(), Some(3));
+/// assert!(chek::try_ok!("x".parse::<i32>(), "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_ok {
+    ($value:expr $(,)?) => (
+        match $value {
+            Ok(value) => Ok(value),
+            Err(error) => Err($crate::__internal::value_failure("ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value), None)),
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Ok(value) => Ok(value),
+            Err(error) => Err($crate::__internal::value_failure("ok", &Err::<(), _>($crate::__debug!(&error)), stringify!($value), Some(format_args!($($msg_args)+)))),
+        }
+    );
+}
+
+/// Returns the contents of a `Result`, or returns early with an error from the
+/// enclosing function if it's an `Err`.
+///
+/// This is the `Result`-returning version of `chek::ok!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. To return the original error instead, just use `?`.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(s: &str) -> Result<i32, chek::Failure> {
+///     Ok(chek::ensure_ok!(s.parse::<i32>()) + 1)
+/// }
+///
+/// fn with_error(s: &str) -> Result<i32, &'static str> {
+///     Ok(chek::ensure_ok!(s.parse::<i32>(), "With an error") + 1)
+/// }
+///
+/// assert_eq!(no_error("1").ok(), Some(2));
+/// assert!(no_error("x").is_err());
+/// assert_eq!(with_error("x"), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_ok {
+    ($value:expr $(,)?) => (
+        match $crate::try_ok!($value) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from(failure)),
+        }
+    );
+    ($value:expr, $err:expr $(,)?) => (
+        match $crate::try_ok!($value) {
+            Ok(value) => value,
+            Err(failure) => return Err(From::from($crate::__ensure_error!($err, failure))),
+        }
+    );
+}
+
+/// Returns the error in a `Result`, panicking if it's `Ok`.
+///
+/// Like `Result::unwrap_err`, but the failure message includes the expression
+/// that produced the `Ok`, and the optional message. Neither the `Ok`
+/// value nor the error need to implement `Debug`, although the `Ok` value is
+/// shown as `<type name: Debug not available here>` on failure if it doesn't.
+///
+/// If a failure handler is installed (see `chek::set_failure_handler`) and it
+/// returns, this panics anyway, since there's no value to return.
+///
+/// A debug-only version is available as `chek::debug_err!`.
+///
+/// Optionally may take an additional message to display on failure, which is
+/// formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// let e = chek::err!("x".parse::<i32>());
+/// let f = chek::err!("y".parse::<i32>(), "With a message");
+/// assert_eq!(e, f);
+/// ```
+#[macro_export]
+macro_rules! err {
+    ($value:expr $(,)?) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                $crate::__value_assert_fail!("err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value));
+                $crate::__internal::no_value("err")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                $crate::__value_assert_fail!("err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value), format_args!($($msg_args)+));
+                $crate::__internal::no_value("err")
+            }
+        }
+    );
+}
+
+/// Same as `chek::err!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, an
+/// `Ok` still panics, but without the value or the message, which are never
+/// formatted.
+///
+/// To skip the check entirely in release builds, see
+/// `chek::debug_err_unchecked!`.
+///
+/// # Example
+///
+/// ```rust
+/// let e = chek::debug_err!("x".parse::<i32>());
+/// let f = chek::debug_err!("y".parse::<i32>(), "With a message");
+/// assert_eq!(e, f);
+/// ```
+#[macro_export]
+macro_rules! debug_err {
+    ($value:expr $(,)?) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value));
+                }
+                $crate::__internal::no_value("debug_err")
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value), format_args!($($msg_args)+));
+                }
+                $crate::__internal::no_value("debug_err")
+            }
+        }
+    );
+}
+
+/// Same as `chek::err!` in debug builds or release builds where the `-C
+/// debug-assertions` was provided to the compiler. For all other builds, an
+/// `Ok` is a `std::hint::unreachable_unchecked()` call. This is `unsafe` to
+/// call in both debug and release builds.
+///
+/// # Example
+///
+/// ```rust
+/// // Obviously, be extremely sure you're correct if you use this.
+/// let e = unsafe { chek::debug_err_unchecked!("x".parse::<i32>(), "optional message") };
+/// assert_eq!(e.to_string(), "invalid digit found in string");
+/// ```
+#[macro_export]
+macro_rules! debug_err_unchecked {
+    ($value:expr $(,)?) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_err_unchecked", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value));
+                    $crate::__internal::no_value("debug_err_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Err(error) => error,
+            Ok(value) => {
+                if cfg!(debug_assertions) {
+                    $crate::__value_assert_fail!("debug_err_unchecked", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value), format_args!($($msg_args)+));
+                    $crate::__internal::no_value("debug_err_unchecked")
+                } else {
+                    $crate::__internal::unreachable_unchecked()
+                }
+            }
+        }
+    );
+}
+
+/// Returns `Ok` with the error in a `Result`, or `Err(chek::Failure)` if it's
+/// `Ok`.
+///
+/// This is the non-panicking version of `chek::err!`.
+///
+/// Optionally may take an additional message to store in the `Failure`, which
+/// is formatted using standard format syntax.
+///
+/// # Example
+///
+/// ```rust
+/// assert!(chek::try_err!("x".parse::<i32>()).is_ok());
+/// assert!(chek::try_err!("3".parse::<i32>(), "With a message").is_err());
+/// ```
+#[macro_export]
+macro_rules! try_err {
+    ($value:expr $(,)?) => (
+        match $value {
+            Err(error) => Ok(error),
+            Ok(value) => Err($crate::__internal::value_failure("err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value), None)),
+        }
+    );
+    ($value:expr, $($msg_args:tt)+) => (
+        match $value {
+            Err(error) => Ok(error),
+            Ok(value) => Err($crate::__internal::value_failure("err", &Ok::<_, ()>($crate::__debug!(&value)), stringify!($value), Some(format_args!($($msg_args)+)))),
+        }
+    );
+}
+
+/// Returns the error in a `Result`, or returns early with an error from the
+/// enclosing function if it's `Ok`.
+///
+/// This is the `Result`-returning version of `chek::err!`.
+///
+/// If no error is provided, the `chek::Failure` describing the problem is
+/// converted to the function's error type using `From`. Otherwise, the provided
+/// error is returned (also converted using `From`), and is only evaluated on
+/// failure. If the error is a function or closure taking a `chek::Failure`,
+/// e.g. a tuple variant like `MyError::Check`, it's called with the `Failure`,
+/// so that the error can keep the details of what failed.
+///
+/// # Example
+///
+/// ```rust
+/// fn no_error(s: &str) -> Result<String, chek::Failure> {
+///     Ok(chek::ensure_err!(s.parse::<i32>()).to_string())
+/// }
+///
+/// fn with_error(s: &str) -> Result<String, &'static str> {
+///     Ok(chek::ensure_err!(s.parse::<i32>(), "With an error").to_string())
+/// }
+///
+/// assert!(no_error("x").is_ok());
+/// assert!(no_error("1").is_err());
+/// assert_eq!(with_error("1"), Err("With an error"));
+/// ```
+#[macro_export]
+macro_rules! ensure_err {
+    ($value:expr $(,)?) => (
+        match $crate::try_err!($value) {
+            Ok(error) => error,
+            Err(failure) => return Err(From::from(failure)),
+        }
+    );
+    ($value:expr, $err:expr $(,)?) => (
+        match $crate::try_err!($value) {
+            Ok(error) => error,
+            Err(failure) => return Err(From::from($crate::__ensure_error!($err, failure))),
+        }
+    );
+}
@@ -0,0 +1,1301 @@
+//! Checks the exact panic message of every assertion, with and without a
+//! message. The `other_mode` test reruns this file with `inline_panics`
+//! toggled, so that the inline and out-of-line failure paths can't drift apart,
+//! and with `diff` and `std` toggled, so that the output is checked with and
+//! without them.
+//!
+//! Where the assertion has a `try_` version, this also checks that the
+//! `Display` output of its `chek::Failure` matches the panic message.
+
+use std::panic::{catch_unwind, UnwindSafe};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Once;
+
+fn panic_message(f: impl FnOnce() + UnwindSafe) -> Option<String> {
+    static QUIET: Once = Once::new();
+    QUIET.call_once(|| std::panic::set_hook(Box::new(|_| {})));
+    let payload = catch_unwind(f).err()?;
+    let msg = match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => payload.downcast_ref::<&str>().expect("unexpected panic payload").to_string(),
+    };
+    Some(msg)
+}
+
+fn cmp(which: &str, left: (&str, &str), right: (&str, &str), msg: Option<&str>) -> String {
+    format!(
+        "assertion failed: `chek::{}!(left, right)`{}\n  left: `{}` = `{}`,\n right: `{}` = `{}`",
+        which,
+        msg.map(|m| format!(": {}", m)).unwrap_or_default(),
+        left.0,
+        left.1,
+        right.0,
+        right.1,
+    )
+}
+
+/// Like `cmp`, but with the `result:` line that the ordering assertions add.
+fn ord(which: &str, left: (&str, &str), right: (&str, &str), result: &str, msg: Option<&str>) -> String {
+    format!("{}\nresult: {}", cmp(which, left, right, msg), result)
+}
+
+fn value(which: &str, value: (&str, &str), msg: Option<&str>) -> String {
+    format!(
+        "assertion failed: `chek::{}!(value)`{}\n value: `{}` = `{}`",
+        which,
+        msg.map(|m| format!(": {}", m)).unwrap_or_default(),
+        value.0,
+        value.1,
+    )
+}
+
+/// `expect_failure!(lt, try_lt, (a, b), expected)` checks that `chek::lt!(a,
+/// b)` panics with the message `expected`, and that `chek::try_lt!(a, b)`
+/// fails with a `Failure` that displays as `expected`.
+///
+/// `expect_failure!(debug debug_lt, (a, b), expected)` checks that
+/// `chek::debug_lt!(a, b)` panics with `expected` if debug assertions are on,
+/// and doesn't panic otherwise.
+macro_rules! expect_failure {
+    ($name:ident, $try_name:ident, ($($args:tt)*), $expected:expr) => {{
+        let expected: String = $expected;
+        let got = panic_message(|| { chek::$name!($($args)*); });
+        assert_eq!(got.as_deref(), Some(&*expected), "chek::{}!({})", stringify!($name), stringify!($($args)*));
+        let failure = chek::$try_name!($($args)*).unwrap_err();
+        assert_eq!(failure.to_string(), expected, "chek::{}!({})", stringify!($try_name), stringify!($($args)*));
+    }};
+    (debug $name:ident, ($($args:tt)*), $expected:expr) => {{
+        let expected: String = $expected;
+        let got = panic_message(|| { chek::$name!($($args)*); });
+        let expected = if cfg!(debug_assertions) { Some(&*expected) } else { None };
+        assert_eq!(got.as_deref(), expected, "chek::{}!({})", stringify!($name), stringify!($($args)*));
+    }};
+}
+
+/// Checks every version of a two-argument assertion, with and without a
+/// message. If a `result` is given, the failure is expected to report it (as
+/// the ordering assertions do).
+macro_rules! expect_cmp_failures {
+    (($left:expr, $right:expr) => ($left_val:expr, $right_val:expr), result: $result:expr, [$($name:ident, $try_name:ident, $debug_name:ident);+]) => {$({
+        let expected = |which: &str, msg| {
+            ord(which, ($left_val, stringify!($left)), ($right_val, stringify!($right)), $result, msg)
+        };
+        expect_cmp_failures!(@check expected, ($left, $right), $name, $try_name, $debug_name);
+    })+};
+    (($left:expr, $right:expr) => ($left_val:expr, $right_val:expr), [$($name:ident, $try_name:ident, $debug_name:ident);+]) => {$({
+        let expected = |which: &str, msg| {
+            cmp(which, ($left_val, stringify!($left)), ($right_val, stringify!($right)), msg)
+        };
+        expect_cmp_failures!(@check expected, ($left, $right), $name, $try_name, $debug_name);
+    })+};
+    (@check $expected:ident, ($left:expr, $right:expr), $name:ident, $try_name:ident, $debug_name:ident) => {{
+        let expected = $expected;
+        expect_failure!($name, $try_name, ($left, $right), expected(stringify!($name), None));
+        expect_failure!($name, $try_name, ($left, $right, "oh {}", "no"), expected(stringify!($name), Some("oh no")));
+        expect_failure!(debug $debug_name, ($left, $right), expected(stringify!($debug_name), None));
+        expect_failure!(debug $debug_name, ($left, $right, "oh {}", "no"), expected(stringify!($debug_name), Some("oh no")));
+    }};
+}
+
+#[test]
+fn exact_messages() {
+    // Spelled out completely once, in case `cmp` and `value` are wrong.
+    let (a, b) = (4, 3);
+    assert_eq!(
+        panic_message(|| chek::lt!(a, b)).unwrap(),
+        "assertion failed: `chek::lt!(left, right)`\n  left: `4` = `a`,\n right: `3` = `b`\nresult: left was greater than right",
+    );
+    assert_eq!(
+        panic_message(|| chek::lt!(a, b, "oh {}", "no")).unwrap(),
+        "assertion failed: `chek::lt!(left, right)`: oh no\n  left: `4` = `a`,\n right: `3` = `b`\nresult: left was greater than right",
+    );
+    let x = 1.0f32;
+    assert_eq!(
+        panic_message(|| chek::almost_zero!(x)).unwrap(),
+        "assertion failed: `chek::almost_zero!(value)`\n value: `1.0` = `x`",
+    );
+    assert_eq!(
+        panic_message(|| chek::almost_zero!(x, "oh {}", "no")).unwrap(),
+        "assertion failed: `chek::almost_zero!(value)`: oh no\n value: `1.0` = `x`",
+    );
+}
+
+#[test]
+fn ordering() {
+    let (four, three) = (4, 3);
+    expect_cmp_failures!((four, three) => ("4", "3"), result: "left was greater than right", [
+        less, try_less, debug_less;
+        lt, try_lt, debug_lt;
+        less_or_equal, try_less_or_equal, debug_less_or_equal;
+        le, try_le, debug_le
+    ]);
+    expect_cmp_failures!((three, four) => ("3", "4"), result: "left was less than right", [
+        greater, try_greater, debug_greater;
+        gt, try_gt, debug_gt;
+        greater_or_equal, try_greater_or_equal, debug_greater_or_equal;
+        ge, try_ge, debug_ge
+    ]);
+    expect_cmp_failures!((three, 3) => ("3", "3"), result: "left was equal to right", [
+        less, try_less, debug_less;
+        lt, try_lt, debug_lt;
+        greater, try_greater, debug_greater;
+        gt, try_gt, debug_gt
+    ]);
+    let (one, nan) = (1.0f64, f64::NAN);
+    expect_cmp_failures!((one, nan) => ("1.0", "NaN"), result: "values are incomparable (partial_cmp returned None)", [
+        less, try_less, debug_less;
+        lt, try_lt, debug_lt;
+        less_or_equal, try_less_or_equal, debug_less_or_equal;
+        le, try_le, debug_le;
+        greater, try_greater, debug_greater;
+        gt, try_gt, debug_gt;
+        greater_or_equal, try_greater_or_equal, debug_greater_or_equal;
+        ge, try_ge, debug_ge
+    ]);
+}
+
+#[test]
+fn equality() {
+    let (s, t) = ("foo", String::from("bar"));
+    expect_cmp_failures!((s, t) => ("\"foo\"", "\"bar\""), [
+        equal, try_equal, debug_equal;
+        eq, try_eq, debug_eq
+    ]);
+    expect_cmp_failures!((s, "foo") => ("\"foo\"", "\"foo\""), [
+        not_equal, try_not_equal, debug_not_equal;
+        ne, try_ne, debug_ne
+    ]);
+
+    // These have no `PartialEq` between them, so they're compared after
+    // dereferencing one or both, but still printed as written.
+    let (boxed, five) = (Box::new(5), 5);
+    expect_cmp_failures!((five, boxed) => ("5", "5"), [
+        not_equal, try_not_equal, debug_not_equal;
+        ne, try_ne, debug_ne
+    ]);
+    let (boxed_str, t) = (Box::<str>::from("foo"), "bar");
+    expect_cmp_failures!((boxed_str, t) => ("\"foo\"", "\"bar\""), [
+        equal, try_equal, debug_equal;
+        eq, try_eq, debug_eq
+    ]);
+
+    chek::eq!(boxed, 5);
+    chek::eq!([1, 2], vec![1, 2]);
+    chek::eq!(std::rc::Rc::new(String::from("foo")), s);
+    chek::eq!(std::borrow::Cow::Borrowed("foo"), String::from("foo"));
+    chek::ne!(Box::new([1, 2]), [1, 3]);
+}
+
+#[test]
+fn mixed_ints() {
+    // `-1i32 as u64` would be `u64::MAX`, so these make sure the comparison
+    // isn't done with a cast.
+    let (neg, zero, max) = (-1i32, 0u64, u64::MAX);
+    expect_cmp_failures!((neg, zero) => ("-1", "0"), result: "left was less than right", [
+        greater, try_greater, debug_greater;
+        gt, try_gt, debug_gt;
+        greater_or_equal, try_greater_or_equal, debug_greater_or_equal;
+        ge, try_ge, debug_ge
+    ]);
+    expect_cmp_failures!((max, neg) => ("18446744073709551615", "-1"), result: "left was greater than right", [
+        less, try_less, debug_less;
+        lt, try_lt, debug_lt;
+        less_or_equal, try_less_or_equal, debug_less_or_equal;
+        le, try_le, debug_le
+    ]);
+    expect_cmp_failures!((max, neg) => ("18446744073709551615", "-1"), [
+        equal, try_equal, debug_equal;
+        eq, try_eq, debug_eq
+    ]);
+    let (len, offset) = (10usize, 10i64);
+    expect_cmp_failures!((len, offset) => ("10", "10"), [
+        not_equal, try_not_equal, debug_not_equal;
+        ne, try_ne, debug_ne
+    ]);
+
+    chek::lt!(neg, zero);
+    chek::le!(i128::MIN, u128::MAX);
+    chek::gt!(u8::MAX, i8::MAX);
+    chek::ge!(len, offset);
+    chek::eq!(255u8, 255i64);
+    chek::ne!(neg, max);
+    // Literals still take the other operand's type when the standard traits
+    // apply, rather than falling back to `i32`.
+    chek::lt!(zero, 5_000_000_000);
+}
+
+#[test]
+fn no_debug() {
+    #[derive(Clone, Copy, PartialEq, PartialOrd)]
+    struct Handle(u32);
+
+    let name = "<output::no_debug::Handle: Debug not available here>";
+    let (a, b) = (Handle(1), Handle(2));
+    expect_cmp_failures!((a, b) => (name, name), [
+        equal, try_equal, debug_equal;
+        eq, try_eq, debug_eq
+    ]);
+    expect_cmp_failures!((b, a) => (name, name), result: "left was greater than right", [
+        less, try_less, debug_less;
+        lt, try_lt, debug_lt
+    ]);
+
+    let some = Some(Handle(3));
+    let some_name = format!("Some({})", name);
+    expect_failure!(none, try_none, (some), value("none", (&some_name, "some"), None));
+    expect_failure!(debug debug_none, (some), value("debug_none", (&some_name, "some"), None));
+    let err: Result<u8, Handle> = Err(Handle(4));
+    expect_failure!(ok, try_ok, (err), value("ok", (&format!("Err({})", name), "err"), None));
+    let ok: Result<Handle, u8> = Ok(Handle(5));
+    expect_failure!(err, try_err, (ok), value("err", (&format!("Ok({})", name), "ok"), None));
+
+    assert_eq!(
+        panic_message(|| chek::matches!(a, Handle(0))),
+        Some(format!("assertion failed: `chek::matches!(a, Handle(0))`\n value: `{}`", name)),
+    );
+
+    // Unsized values that implement `Debug` are still shown.
+    let bytes: &[u8] = &[1, 2];
+    expect_cmp_failures!((*bytes, [1u8, 2]) => ("[1, 2]", "[1, 2]"), [
+        not_equal, try_not_equal, debug_not_equal;
+        ne, try_ne, debug_ne
+    ]);
+    let s: &str = "foo";
+    expect_cmp_failures!((*s, *"bar") => ("\"foo\"", "\"bar\""), [
+        equal, try_equal, debug_equal;
+        eq, try_eq, debug_eq
+    ]);
+}
+
+#[test]
+fn range() {
+    let (x, lo, hi) = (5, 1, 5);
+    let expected = |which: &str, range: (&str, &str), result: &str, msg| ord(which, ("5", "x"), range, result, msg);
+    let below = |which: &str, msg| expected(which, ("6..", "hi + 1.."), "left was less than the start of right", msg);
+    expect_failure!(in_range, try_in_range, (x, hi + 1..), below("in_range", None));
+    expect_failure!(in_range, try_in_range, (x, hi + 1.., "oh {}", "no"), below("in_range", Some("oh no")));
+    expect_failure!(debug debug_in_range, (x, hi + 1..), below("debug_in_range", None));
+    expect_failure!(debug debug_in_range, (x, hi + 1.., "oh {}", "no"), below("debug_in_range", Some("oh no")));
+
+    let at_end = |which: &str, msg| expected(which, ("1..5", "lo..hi"), "left was equal to the end of right, which is excluded", msg);
+    expect_failure!(in_range, try_in_range, (x, lo..hi), at_end("in_range", None));
+    expect_failure!(in_range, try_in_range, (x, lo..hi, "oh {}", "no"), at_end("in_range", Some("oh no")));
+    expect_failure!(debug debug_in_range, (x, lo..hi), at_end("debug_in_range", None));
+    expect_failure!(debug debug_in_range, (x, lo..hi, "oh {}", "no"), at_end("debug_in_range", Some("oh no")));
+
+    let above = |which: &str, msg| expected(which, ("..=4", "..=hi - 1"), "left was greater than the end of right", msg);
+    expect_failure!(in_range, try_in_range, (x, ..=hi - 1), above("in_range", None));
+    expect_failure!(in_range, try_in_range, (x, ..=hi - 1, "oh {}", "no"), above("in_range", Some("oh no")));
+
+    let within = |which: &str, msg| expected(which, ("1..=5", "lo..=hi"), "left was within right", msg);
+    expect_failure!(not_in_range, try_not_in_range, (x, lo..=hi), within("not_in_range", None));
+    expect_failure!(not_in_range, try_not_in_range, (x, lo..=hi, "oh {}", "no"), within("not_in_range", Some("oh no")));
+    expect_failure!(debug debug_not_in_range, (x, lo..=hi), within("debug_not_in_range", None));
+    expect_failure!(debug debug_not_in_range, (x, lo..=hi, "oh {}", "no"), within("debug_not_in_range", Some("oh no")));
+    let everything = |which: &str, msg| expected(which, ("..", ".."), "left was within right", msg);
+    expect_failure!(not_in_range, try_not_in_range, (x, ..), everything("not_in_range", None));
+
+    // Excluded start bounds are only available through `(Bound, Bound)`.
+    use std::ops::Bound::{Excluded, Unbounded};
+    let excluded = (Excluded(5), Unbounded);
+    let at_start = |which: &str, msg| {
+        expected(which, ("(Excluded(5), Unbounded)", "excluded"), "left was equal to the start of right, which is excluded", msg)
+    };
+    expect_failure!(in_range, try_in_range, (x, excluded), at_start("in_range", None));
+
+    let nan = f64::NAN;
+    let incomparable = |which: &str, msg| {
+        ord(which, ("NaN", "nan"), ("0.0..1.0", "0.0..1.0"), "left is incomparable to the start of right (partial_cmp returned None)", msg)
+    };
+    expect_failure!(in_range, try_in_range, (nan, 0.0..1.0), incomparable("in_range", None));
+    expect_failure!(in_range, try_in_range, (nan, 0.0..1.0, "oh {}", "no"), incomparable("in_range", Some("oh no")));
+    let incomparable_end = |which: &str, msg| {
+        ord(which, ("NaN", "nan"), ("..1.0", "..1.0"), "left is incomparable to the end of right (partial_cmp returned None)", msg)
+    };
+    expect_failure!(in_range, try_in_range, (nan, ..1.0), incomparable_end("in_range", None));
+}
+
+#[test]
+fn contains() {
+    let primes = [2, 3, 5, 7];
+    let expected = |which: &str, needle: (&str, &str), result: &str, msg| ord(which, ("[2, 3, 5, 7]", "primes"), needle, result, msg);
+    let missing = |which: &str, msg| expected(which, ("4", "4"), "left (length 4) does not contain right", msg);
+    expect_failure!(contains, try_contains, (primes, 4), missing("contains", None));
+    expect_failure!(contains, try_contains, (primes, 4, "oh {}", "no"), missing("contains", Some("oh no")));
+    expect_failure!(debug debug_contains, (primes, 4), missing("debug_contains", None));
+    expect_failure!(debug debug_contains, (primes, 4, "oh {}", "no"), missing("debug_contains", Some("oh no")));
+
+    let found = |which: &str, msg| expected(which, ("5", "5"), "left (length 4) contains right at index 2", msg);
+    expect_failure!(not_contains, try_not_contains, (primes, 5), found("not_contains", None));
+    expect_failure!(not_contains, try_not_contains, (primes, 5, "oh {}", "no"), found("not_contains", Some("oh no")));
+    expect_failure!(debug debug_not_contains, (primes, 5), found("debug_not_contains", None));
+    expect_failure!(debug debug_not_contains, (primes, 5, "oh {}", "no"), found("debug_not_contains", Some("oh no")));
+
+    // Strings are searched for substrings or `char`s, and report byte offsets.
+    let s = "h\u{e9}llo world";
+    let in_s = |which: &str, needle: (&str, &str), result: &str| ord(which, ("\"h\u{e9}llo world\"", "s"), needle, result, None);
+    expect_failure!(contains, try_contains, (s, "wx"), in_s("contains", ("\"wx\"", "\"wx\""), "left (length 12) does not contain right"));
+    expect_failure!(not_contains, try_not_contains, (s, 'w'), in_s("not_contains", ("'w'", "'w'"), "left (length 12) contains right at index 7"));
+    chek::contains!(s, "llo");
+    chek::not_contains!(s[3..], 'h');
+
+    // Only the start of a long collection is shown.
+    let long: Vec<u32> = (0..100).collect();
+    let preview = "[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 1...";
+    let expected = ord("contains", (preview, "long"), ("100", "100"), "left (length 100) does not contain right", None);
+    expect_failure!(contains, try_contains, (long, 100), expected);
+    chek::contains!(long, 99);
+    chek::not_contains!(&long[..50], 50);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn contains_keys() {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    let ports: BTreeMap<String, u16> = [("http", 80), ("https", 443)].iter().map(|&(k, v)| (k.to_string(), v)).collect();
+    let expected = |which: &str, key: (&str, &str), result: &str| {
+        ord(which, ("{\"http\": 80, \"https\": 443}", "ports"), key, result, None)
+    };
+    let missing = |which: &str| expected(which, ("\"ftp\"", "\"ftp\""), "left (length 2) does not contain right");
+    expect_failure!(contains_key, try_contains_key, (ports, "ftp"), missing("contains_key"));
+    expect_failure!(debug debug_contains_key, (ports, "ftp"), missing("debug_contains_key"));
+    expect_failure!(contains, try_contains, (ports, "ftp"), missing("contains"));
+    let found = expected("not_contains", ("\"http\"", "\"http\""), "left (length 2) contains right under the key `\"http\"`");
+    expect_failure!(not_contains, try_not_contains, (ports, "http"), found);
+
+    let names: BTreeSet<&str> = ["ada", "grace"].iter().copied().collect();
+    let found = ord("not_contains", ("{\"ada\", \"grace\"}", "names"), ("\"ada\"", "\"ada\""), "left (length 2) contains right as the element `\"ada\"`", None);
+    expect_failure!(not_contains, try_not_contains, (names, "ada"), found);
+
+    chek::contains_key!(ports, "http");
+    chek::contains_key!(&ports, String::from("https"));
+    chek::contains!(names, "grace");
+    chek::contains!(vec![String::from("a")], "a");
+    chek::contains!(String::from("abc"), String::from("bc"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn contains_hashed() {
+    use std::collections::{HashMap, HashSet};
+
+    let ports: HashMap<&str, u16> = [("http", 80)].iter().copied().collect();
+    let found = ord("not_contains", ("{\"http\": 80}", "ports"), ("\"http\"", "\"http\""), "left (length 1) contains right under the key `\"http\"`", None);
+    expect_failure!(not_contains, try_not_contains, (ports, "http"), found);
+    let missing = ord("contains_key", ("{\"http\": 80}", "ports"), ("\"ftp\"", "\"ftp\""), "left (length 1) does not contain right", None);
+    expect_failure!(contains_key, try_contains_key, (ports, "ftp"), missing);
+
+    let names: HashSet<String> = ["ada"].iter().map(|s| s.to_string()).collect();
+    chek::contains!(names, "ada");
+    chek::not_contains!(names, "grace");
+}
+
+#[test]
+fn slice_eq() {
+    let a: Vec<u32> = (0..100).collect();
+    let mut b = a.clone();
+    b[50] = 0;
+    let expected = |which: &str, msg| {
+        ord(
+            which,
+            ("[..., 47, 48, 49, 50, 51, 52, 53, ...]", "a"),
+            ("[..., 47, 48, 49, 0, 51, 52, 53, ...]", "b"),
+            "first difference at index 50 (both have length 100)",
+            msg,
+        )
+    };
+    expect_failure!(slice_eq, try_slice_eq, (a, b), expected("slice_eq", None));
+    expect_failure!(slice_eq, try_slice_eq, (a, b, "oh {}", "no"), expected("slice_eq", Some("oh no")));
+    expect_failure!(debug debug_slice_eq, (a, b), expected("debug_slice_eq", None));
+    expect_failure!(debug debug_slice_eq, (a, b, "oh {}", "no"), expected("debug_slice_eq", Some("oh no")));
+
+    let expected = ord(
+        "slice_eq",
+        ("[0, 1, 2, 3, 4]", "&a[..5]"),
+        ("[0, 1, 9]", "[0, 1, 9]"),
+        "first difference at index 2 (lengths are 5 and 3)",
+        None,
+    );
+    expect_failure!(slice_eq, try_slice_eq, (&a[..5], [0, 1, 9]), expected);
+
+    let expected = ord(
+        "slice_eq",
+        ("[0, 1, 2]", "&a[..3]"),
+        ("[0, 1, 2, 3, 4]", "&a[..5]"),
+        "lengths are 3 and 5, but the first 3 elements are equal",
+        None,
+    );
+    expect_failure!(slice_eq, try_slice_eq, (&a[..3], &a[..5]), expected);
+
+    let expected = ord("slice_eq", ("[]", "&a[..0]"), ("[0]", "&a[..1]"), "lengths are 0 and 1, but the first 0 elements are equal", None);
+    expect_failure!(slice_eq, try_slice_eq, (&a[..0], &a[..1]), expected);
+}
+
+#[test]
+fn same_elements() {
+    let (x, y) = ([1, 2, 2, 4], [2, 1, 3, 2, 2]);
+    let expected = |which: &str, msg| format!("{}\nmissing: `3`\n  extra: `4`\n counts: `2` (left 2, right 3)", cmp(which, ("[1, 2, 2, 4]", "x"), ("[2, 1, 3, 2, 2]", "y"), msg));
+    expect_failure!(same_elements, try_same_elements, (x, y), expected("same_elements", None));
+    expect_failure!(same_elements, try_same_elements, (x, y, "oh {}", "no"), expected("same_elements", Some("oh no")));
+    expect_failure!(debug debug_same_elements, (x, y), expected("debug_same_elements", None));
+    expect_failure!(debug debug_same_elements, (x, y, "oh {}", "no"), expected("debug_same_elements", Some("oh no")));
+
+    // Elements that are only `PartialEq` are counted by scanning, and listed
+    // in the order they first appear, with how many times they're missing.
+    let (a, b) = (vec![0.5, 0.25], [0.25, 1.5, 0.5, 1.5]);
+    let expected = format!("{}\nmissing: `1.5` (x2)", cmp("same_elements", ("[0.5, 0.25]", "a"), ("[0.25, 1.5, 0.5, 1.5]", "b"), None));
+    expect_failure!(same_elements, try_same_elements, (a, b), expected);
+
+    // NaN isn't equal to itself, so it's never found in the other slice.
+    let (nan, one) = ([f64::NAN, 1.0], [1.0]);
+    let expected = format!("{}\n  extra: `NaN`", cmp("same_elements", ("[NaN, 1.0]", "nan"), ("[1.0]", "one"), None));
+    expect_failure!(same_elements, try_same_elements, (nan, one), expected);
+    let (one_nan, two_nans) = ([f64::NAN], [f64::NAN, f64::NAN]);
+    let expected = format!(
+        "{}\nmissing: `NaN`, `NaN`\n  extra: `NaN`",
+        cmp("same_elements", ("[NaN]", "one_nan"), ("[NaN, NaN]", "two_nans"), None)
+    );
+    expect_failure!(same_elements, try_same_elements, (one_nan, two_nans), expected);
+
+    // Elements that are `Ord` but not `Hash` are sorted (with `alloc`), but
+    // still listed in the order they first appear.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Job(u8);
+    let (jobs, done) = ([Job(9), Job(1), Job(5)], [Job(5), Job(1), Job(1)]);
+    let expected = format!(
+        "{}\n  extra: `Job(9)`\n counts: `Job(1)` (left 1, right 2)",
+        cmp("same_elements", ("[Job(9), Job(1), Job(5)]", "jobs"), ("[Job(5), Job(1), Job(1)]", "done"), None)
+    );
+    expect_failure!(same_elements, try_same_elements, (jobs, done), expected);
+
+    // Only the first few elements are listed on each line.
+    let ids: Vec<u8> = (0..10).collect();
+    let expected = format!(
+        "{}\n  extra: `0`, `1`, `2`, `3`, `4`, `5`, `6`, `7`, ... and 2 more",
+        cmp("same_elements", ("[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]", "ids"), ("[]", "[]"), None)
+    );
+    expect_failure!(same_elements, try_same_elements, (ids, []), expected);
+
+    chek::same_elements!(jobs, [Job(5), Job(9), Job(1)]);
+    chek::same_elements!(vec![3, 1, 2, 1], [1, 1, 2, 3]);
+    chek::same_elements!(&a[..0], Vec::<f64>::new());
+}
+
+#[test]
+fn bytes_eq() {
+    let frame = b"GET /index.html\r\n".to_vec();
+    let expected = |which: &str, msg: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(left, right)`{}", which, msg),
+            "  left: `<17 bytes>` = `frame`,",
+            " right: `<15 bytes>` = `b\"GET /index.htm\\n\"`",
+            "result: first difference at offset 14 (0xe), lengths are 17 and 15",
+            "00000000: 47 45 54 20 2f 69 6e 64  GET /ind | 47 45 54 20 2f 69 6e 64  GET /ind",
+            "00000008: 65 78 2e 68 74 6d 6c 0d  ex.html. | 65 78 2e 68 74 6d 0a     ex.htm.",
+            "                            ^^ ^^        ^^                     ^^ ^^        ^^",
+            "00000010: 0a                       .        |",
+            "          ^^                       ^          ^^                       ^",
+        ]
+        .join("\n")
+    };
+    assert_eq!(panic_message(|| chek::bytes_eq!(frame, b"GET /index.htm\n")), Some(expected("bytes_eq", "")));
+    assert_eq!(
+        panic_message(|| chek::bytes_eq!(frame, b"GET /index.htm\n", "oh {}", "no")),
+        Some(expected("bytes_eq", ": oh no")),
+    );
+    let debug_expected = |msg| if cfg!(debug_assertions) { Some(expected("debug_bytes_eq", msg)) } else { None };
+    assert_eq!(panic_message(|| chek::debug_bytes_eq!(frame, b"GET /index.htm\n")), debug_expected(""));
+    assert_eq!(
+        panic_message(|| chek::debug_bytes_eq!(frame, b"GET /index.htm\n", "oh {}", "no")),
+        debug_expected(": oh no"),
+    );
+    // Without `alloc`, the `Failure` only has room for part of the dump.
+    let failure = chek::try_bytes_eq!(frame, b"GET /index.htm\n", "oh {}", "no").unwrap_err();
+    assert_eq!((failure.left_value(), failure.right_value()), ("<17 bytes>", Some("<15 bytes>")));
+    if cfg!(feature = "alloc") {
+        assert_eq!(failure.to_string(), expected("bytes_eq", ": oh no"));
+    }
+
+    // Long buffers only show the rows around the first difference.
+    let (a, mut b) = ([0u8; 4096], [0u8; 4096]);
+    b[1000] = 1;
+    b[1003] = b'x';
+    let rows = "00000000: 00 00 00 00 00 00 00 00  ........ | 00 00 00 00 00 00 00 00  ........";
+    let row = |offset: &str| rows.replacen("00000000", offset, 1);
+    assert_eq!(
+        panic_message(|| chek::bytes_eq!(a, b)).unwrap(),
+        [
+            "assertion failed: `chek::bytes_eq!(left, right)`",
+            "  left: `<4096 bytes>` = `a`,",
+            " right: `<4096 bytes>` = `b`",
+            "result: first difference at offset 1000 (0x3e8), both have length 4096",
+            "...",
+            &row("000003e0"),
+            "000003e8: 00 00 00 00 00 00 00 00  ........ | 01 00 00 78 00 00 00 00  ...x....",
+            "          ^^       ^^              ^  ^       ^^       ^^              ^  ^",
+            &row("000003f0"),
+            &row("000003f8"),
+            &row("00000400"),
+            &row("00000408"),
+            &row("00000410"),
+            &row("00000418"),
+            "...",
+        ]
+        .join("\n"),
+    );
+}
+
+#[test]
+fn diff() {
+    let (s, t) = ("one\ntwo\nthree", "one\n2\nthree");
+    let expected = |which: &str, msg| {
+        let mut expected = cmp(which, ("\"one\\ntwo\\nthree\"", "s"), ("\"one\\n2\\nthree\"", "t"), msg);
+        if cfg!(feature = "diff") {
+            expected += "\ndiff (-left, +right):\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three";
+        }
+        expected
+    };
+    expect_failure!(equal, try_equal, (s, t), expected("equal", None));
+    expect_failure!(eq, try_eq, (s, t, "oh {}", "no"), expected("eq", Some("oh no")));
+    expect_failure!(debug debug_equal, (s, t), expected("debug_equal", None));
+    expect_failure!(debug debug_eq, (s, t, "oh {}", "no"), expected("debug_eq", Some("oh no")));
+
+    let (v, w) = (vec![1, 2, 3], vec![1, 2, 4, 3]);
+    let mut expected = cmp("eq", ("[1, 2, 3]", "v"), ("[1, 2, 4, 3]", "w"), None);
+    if cfg!(feature = "diff") {
+        expected += "\ndiff (-left, +right):\n@@ -1,5 +1,6 @@\n [\n     1,\n     2,\n+    4,\n     3,\n ]";
+    }
+    expect_failure!(eq, try_eq, (v, w), expected);
+}
+
+#[cfg(feature = "diff")]
+#[test]
+fn diff_limits() {
+    // Values that aren't equal to themselves can have identical `Debug` output.
+    let (v, w) = (vec![f64::NAN, 1.0], vec![f64::NAN, 1.0]);
+    let expected = cmp("eq", ("[NaN, 1.0]", "v"), ("[NaN, 1.0]", "w"), None) + "\ndiff (-left, +right): none, both have the same `Debug` output";
+    expect_failure!(eq, try_eq, (v, w), expected);
+
+    // Past `MAX_EDITS`, every line in between is changed, but only the first
+    // few are shown.
+    let s: String = (0..600).map(|i| format!("a{}\n", i)).collect();
+    let t: String = (0..600).map(|i| format!("b{}\n", i)).collect();
+    let mut expected = cmp("eq", (&format!("{:?}", s), "s"), (&format!("{:?}", t), "t"), None);
+    expected += "\ndiff (-left, +right):\n@@ -1,601 +1,601 @@";
+    for i in 0..200 {
+        expected += &format!("\n-a{}", i);
+    }
+    expected += "\n... and 1001 more lines";
+    expect_failure!(eq, try_eq, (s, t), expected);
+}
+
+#[test]
+fn almost_equal() {
+    let (x, y) = (1.0f32, 2.0f32);
+    expect_cmp_failures!((x, y) => ("1.0", "2.0"), [
+        almost_equal, try_almost_equal, debug_almost_equal
+    ]);
+    expect_cmp_failures!((x, x + f32::EPSILON) => ("1.0", "1.0000001"), [
+        not_almost_equal, try_not_almost_equal, debug_not_almost_equal
+    ]);
+}
+
+#[test]
+fn almost_equal_aggregates() {
+    #[derive(Debug, chek::AlmostEq)]
+    struct Quat {
+        v: [f32; 3],
+        w: f32,
+    }
+
+    let (a, b) = (Quat { v: [0.0; 3], w: 1.0 }, Quat { v: [0.0, 0.5, 0.0], w: 1.0 });
+    let expected = |which: &str, msg: Option<&str>| {
+        format!(
+            "{}\nresult: `.v[1]` differs: `0.0` vs `0.5`",
+            cmp(which, ("Quat { v: [0.0, 0.0, 0.0], w: 1.0 }", "a"), ("Quat { v: [0.0, 0.5, 0.0], w: 1.0 }", "b"), msg),
+        )
+    };
+    expect_failure!(almost_equal, try_almost_equal, (a, b), expected("almost_equal", None));
+    expect_failure!(almost_equal, try_almost_equal, (a, b, "oh {}", "no"), expected("almost_equal", Some("oh no")));
+    expect_failure!(debug debug_almost_equal, (a, b), expected("debug_almost_equal", None));
+    expect_failure!(debug debug_almost_equal, (a, b, "oh {}", "no"), expected("debug_almost_equal", Some("oh no")));
+    chek::almost_equal!(a, Quat { v: [0.0; 3], w: 1.0000001 });
+    chek::not_almost_equal!(a, b);
+
+    let (short, long) = (vec![(1.0, Some(2.0))], vec![(1.0, Some(2.0)), (3.0, None)]);
+    expect_failure!(
+        almost_equal,
+        try_almost_equal,
+        (short[..], long[..]),
+        format!(
+            "{}\nresult: `.len()` differs: `1` vs `2`",
+            cmp("almost_equal", ("[(1.0, Some(2.0))]", "short[..]"), ("[(1.0, Some(2.0)), (3.0, None)]", "long[..]"), None),
+        )
+    );
+    expect_failure!(
+        almost_equal,
+        try_almost_equal,
+        (long[..1], [(1.0, None)][..]),
+        format!(
+            "{}\nresult: `[0].1` differs: `Some(2.0)` vs `None`",
+            cmp("almost_equal", ("[(1.0, Some(2.0))]", "long[..1]"), ("[(1.0, None)]", "[(1.0, None)][..]"), None),
+        )
+    );
+}
+
+#[test]
+fn tolerance() {
+    let (a, b) = (1.0f32, 1.5f32);
+    let expected = |which: &str, msg: Option<&str>, limit: (&str, &str)| {
+        format!(
+            "{}\n  diff: `0.5` (absolute), `0.33333334` (relative), `4194304` (ulps)\n limit: `{}` ({})",
+            cmp(which, ("1.0", "a"), ("1.5", "b"), msg),
+            limit.0,
+            limit.1,
+        )
+    };
+
+    expect_failure!(abs_diff_le, try_abs_diff_le, (a, b, 0.25), expected("abs_diff_le", None, ("0.25", "absolute")));
+    expect_failure!(abs_diff_le, try_abs_diff_le, (a, b, 0.25, "oh {}", "no"), expected("abs_diff_le", Some("oh no"), ("0.25", "absolute")));
+    expect_failure!(debug debug_abs_diff_le, (a, b, 0.25), expected("debug_abs_diff_le", None, ("0.25", "absolute")));
+    expect_failure!(debug debug_abs_diff_le, (a, b, 0.25, "oh {}", "no"), expected("debug_abs_diff_le", Some("oh no"), ("0.25", "absolute")));
+
+    expect_failure!(rel_diff_le, try_rel_diff_le, (a, b, 0.25), expected("rel_diff_le", None, ("0.25", "relative")));
+    expect_failure!(rel_diff_le, try_rel_diff_le, (a, b, 0.25, "oh {}", "no"), expected("rel_diff_le", Some("oh no"), ("0.25", "relative")));
+    expect_failure!(debug debug_rel_diff_le, (a, b, 0.25), expected("debug_rel_diff_le", None, ("0.25", "relative")));
+    expect_failure!(debug debug_rel_diff_le, (a, b, 0.25, "oh {}", "no"), expected("debug_rel_diff_le", Some("oh no"), ("0.25", "relative")));
+
+    expect_failure!(ulps_le, try_ulps_le, (a, b, 1000), expected("ulps_le", None, ("1000", "ulps")));
+    expect_failure!(ulps_le, try_ulps_le, (a, b, 1000, "oh {}", "no"), expected("ulps_le", Some("oh no"), ("1000", "ulps")));
+    expect_failure!(debug debug_ulps_le, (a, b, 1000), expected("debug_ulps_le", None, ("1000", "ulps")));
+    expect_failure!(debug debug_ulps_le, (a, b, 1000, "oh {}", "no"), expected("debug_ulps_le", Some("oh no"), ("1000", "ulps")));
+
+    expect_failure!(almost_equal, try_almost_equal, (a, b, tol = 1e-3), expected("almost_equal", None, ("0.001", "relative")));
+    expect_failure!(
+        almost_equal,
+        try_almost_equal,
+        (a, b, tol = 1e-3, "oh {}", "no"),
+        expected("almost_equal", Some("oh no"), ("0.001", "relative"))
+    );
+    expect_failure!(debug debug_almost_equal, (a, b, tol = 1e-3), expected("debug_almost_equal", None, ("0.001", "relative")));
+    expect_failure!(
+        debug debug_almost_equal,
+        (a, b, tol = 1e-3, "oh {}", "no"),
+        expected("debug_almost_equal", Some("oh no"), ("0.001", "relative"))
+    );
+
+    // There's no ULP distance to a NaN.
+    expect_failure!(
+        ulps_le,
+        try_ulps_le,
+        (a, f32::NAN, 1),
+        format!(
+            "{}\n  diff: `NaN` (absolute), `NaN` (relative)\n limit: `1` (ulps)",
+            cmp("ulps_le", ("1.0", "a"), ("NaN", "f32::NAN"), None),
+        )
+    );
+    // Zeros of either sign are the same value, and the closest floats on either
+    // side of zero are two ulps apart.
+    chek::ulps_le!(0.0, -0.0, 0);
+    let tiny = f64::from_bits(1);
+    chek::ulps_le!(tiny, -tiny, 2);
+    assert!(chek::try_ulps_le!(tiny, -tiny, 1).is_err());
+    chek::ulps_le!(f64::INFINITY, f64::MAX, 1);
+    chek::abs_diff_le!(f64::INFINITY, f64::INFINITY, 0.0);
+    chek::rel_diff_le!(0.0, 0.0, 0.0);
+}
+
+#[test]
+fn all_almost() {
+    let a: Vec<f32> = (0..100).map(|i| i as f32).collect();
+    let mut b = a.clone();
+    b[50] = 0.5;
+    b[70] = 0.5;
+    let expected = |which: &str, msg| {
+        ord(
+            which,
+            ("[..., 47.0, 48.0, 49.0, 50.0, 51.0, 52.0, 53.0, ...]", "a"),
+            ("[..., 47.0, 48.0, 49.0, 0.5, 51.0, 52.0, 53.0, ...]", "b"),
+            "2 of 100 elements differ, the first at index 50: `50.0` vs `0.5`",
+            msg,
+        )
+    };
+    expect_failure!(all_almost_equal, try_all_almost_equal, (a, b), expected("all_almost_equal", None));
+    expect_failure!(all_almost_equal, try_all_almost_equal, (a, b, "oh {}", "no"), expected("all_almost_equal", Some("oh no")));
+    expect_failure!(debug debug_all_almost_equal, (a, b), expected("debug_all_almost_equal", None));
+    expect_failure!(debug debug_all_almost_equal, (a, b, "oh {}", "no"), expected("debug_all_almost_equal", Some("oh no")));
+
+    let expected = ord(
+        "all_almost_equal",
+        ("[0.0, 1.0, 2.0]", "&a[..3]"),
+        ("[0.0, 1.5]", "[0.0, 1.5]"),
+        "lengths are 3 and 2, and 1 of the first 2 elements differ, the first at index 1: `1.0` vs `1.5`",
+        None,
+    );
+    expect_failure!(all_almost_equal, try_all_almost_equal, (&a[..3], [0.0, 1.5]), expected);
+
+    let expected = ord(
+        "all_almost_equal",
+        ("[0.0, 1.0]", "&a[..2]"),
+        ("[0.0, 1.0, 2.0]", "&b[..3]"),
+        "lengths are 2 and 3, but the first 2 elements are almost equal",
+        None,
+    );
+    expect_failure!(all_almost_equal, try_all_almost_equal, (&a[..2], &b[..3]), expected);
+
+    // Almost equal elements pass, as with `chek::almost_equal!`.
+    let c: Vec<f32> = a.iter().map(|x| x * (1.0 + f32::EPSILON)).collect();
+    chek::all_almost_equal!(a, c);
+
+    let residuals = [0.0, 1e-20, 0.5, 0.0, -0.25, 0.0, 0.0, 0.0, 0.0];
+    let expected = |which: &str, msg| {
+        format!(
+            "{}\nresult: 2 of 9 elements are not almost zero, the first at index 2: `0.5`",
+            value(which, ("[0.0, 1e-20, 0.5, 0.0, -0.25, 0.0, ...]", "residuals"), msg),
+        )
+    };
+    expect_failure!(all_almost_zero, try_all_almost_zero, (residuals), expected("all_almost_zero", None));
+    expect_failure!(all_almost_zero, try_all_almost_zero, (residuals, "oh {}", "no"), expected("all_almost_zero", Some("oh no")));
+    expect_failure!(debug debug_all_almost_zero, (residuals), expected("debug_all_almost_zero", None));
+    expect_failure!(debug debug_all_almost_zero, (residuals, "oh {}", "no"), expected("debug_all_almost_zero", Some("oh no")));
+    chek::all_almost_zero!(&residuals[..2]);
+}
+
+#[test]
+fn exact() {
+    let (zero, neg_zero) = (0.0f32, -0.0f32);
+    let expected = |which: &str, msg| {
+        format!(
+            "{}\n  bits: `0x00000000` vs `0x80000000`\nfields: sign `0` vs `1`, exponent `0x00` vs `0x00`, mantissa `0x000000` vs `0x000000`",
+            cmp(which, ("0.0", "zero"), ("-0.0", "neg_zero"), msg),
+        )
+    };
+    expect_failure!(bits_eq, try_bits_eq, (zero, neg_zero), expected("bits_eq", None));
+    expect_failure!(bits_eq, try_bits_eq, (zero, neg_zero, "oh {}", "no"), expected("bits_eq", Some("oh no")));
+    expect_failure!(debug debug_bits_eq, (zero, neg_zero), expected("debug_bits_eq", None));
+    expect_failure!(debug debug_bits_eq, (zero, neg_zero, "oh {}", "no"), expected("debug_bits_eq", Some("oh no")));
+
+    let expected = format!(
+        "{}\n  bits: `0x3ff8000000000000` vs `0x3ff8000000000001`\nfields: sign `0` vs `0`, exponent `0x3ff` vs `0x3ff`, mantissa `0x8000000000000` vs `0x8000000000001`",
+        cmp("bits_eq", ("1.5", "1.5f64"), ("1.5000000000000002", "next"), None),
+    );
+    let next = f64::from_bits(0x3ff8000000000001);
+    assert_eq!(panic_message(|| chek::bits_eq!(1.5f64, next)), Some(expected.clone()));
+    // Without `alloc`, the `Failure` only has room for part of the fields.
+    if cfg!(feature = "alloc") {
+        assert_eq!(chek::try_bits_eq!(1.5f64, next).unwrap_err().to_string(), expected);
+    }
+
+    let (nan, neg_nan) = (f64::NAN, -f64::NAN);
+    let expected = |which: &str, (left, right): ((&str, &str), (&str, &str)), bits: &str, result: &str, msg| {
+        format!("{}\n  bits: {}\nresult: left was {} right in the total order", cmp(which, left, right, msg), bits, result)
+    };
+    let bits = "`0x7ff8000000000000` vs `0xfff8000000000000`";
+    let (left, right) = (("NaN", "nan"), ("NaN", "neg_nan"));
+    expect_failure!(total_lt, try_total_lt, (nan, neg_nan), expected("total_lt", (left, right), bits, "greater than", None));
+    expect_failure!(total_lt, try_total_lt, (nan, neg_nan, "oh {}", "no"), expected("total_lt", (left, right), bits, "greater than", Some("oh no")));
+    expect_failure!(debug debug_total_lt, (nan, neg_nan), expected("debug_total_lt", (left, right), bits, "greater than", None));
+    expect_failure!(debug debug_total_lt, (nan, neg_nan, "oh {}", "no"), expected("debug_total_lt", (left, right), bits, "greater than", Some("oh no")));
+
+    expect_failure!(total_le, try_total_le, (nan, neg_nan), expected("total_le", (left, right), bits, "greater than", None));
+    expect_failure!(total_le, try_total_le, (nan, neg_nan, "oh {}", "no"), expected("total_le", (left, right), bits, "greater than", Some("oh no")));
+    expect_failure!(debug debug_total_le, (nan, neg_nan), expected("debug_total_le", (left, right), bits, "greater than", None));
+    expect_failure!(debug debug_total_le, (nan, neg_nan, "oh {}", "no"), expected("debug_total_le", (left, right), bits, "greater than", Some("oh no")));
+
+    let bits = "`0xfff8000000000000` vs `0x7ff8000000000000`";
+    let (left, right) = (("NaN", "neg_nan"), ("NaN", "nan"));
+    expect_failure!(total_eq, try_total_eq, (neg_nan, nan), expected("total_eq", (left, right), bits, "less than", None));
+    expect_failure!(total_eq, try_total_eq, (neg_nan, nan, "oh {}", "no"), expected("total_eq", (left, right), bits, "less than", Some("oh no")));
+    expect_failure!(debug debug_total_eq, (neg_nan, nan), expected("debug_total_eq", (left, right), bits, "less than", None));
+    expect_failure!(debug debug_total_eq, (neg_nan, nan, "oh {}", "no"), expected("debug_total_eq", (left, right), bits, "less than", Some("oh no")));
+
+    chek::bits_eq!(nan, nan);
+    chek::total_eq!(neg_nan, neg_nan);
+    chek::total_lt!(neg_nan, f64::NEG_INFINITY);
+    chek::total_lt!(neg_zero, zero);
+    chek::total_le!(zero, zero);
+    assert!(chek::try_total_le!(zero, neg_zero).is_err());
+}
+
+#[test]
+fn almost_zero() {
+    let (big, tiny) = (1.0f64, 1e-20f64);
+    expect_failure!(almost_zero, try_almost_zero, (big), value("almost_zero", ("1.0", "big"), None));
+    expect_failure!(almost_zero, try_almost_zero, (big, "oh {}", "no"), value("almost_zero", ("1.0", "big"), Some("oh no")));
+    expect_failure!(debug debug_almost_zero, (big), value("debug_almost_zero", ("1.0", "big"), None));
+    expect_failure!(debug debug_almost_zero, (big, "oh {}", "no"), value("debug_almost_zero", ("1.0", "big"), Some("oh no")));
+
+    expect_failure!(almost_zero_with, try_almost_zero_with, (big, 0.5), value("almost_zero_with", ("1.0", "big"), None));
+    expect_failure!(almost_zero_with, try_almost_zero_with, (big, 0.5, "oh {}", "no"), value("almost_zero_with", ("1.0", "big"), Some("oh no")));
+    expect_failure!(debug debug_almost_zero_with, (big, 0.5), value("debug_almost_zero_with", ("1.0", "big"), None));
+    expect_failure!(debug debug_almost_zero_with, (big, 0.5, "oh {}", "no"), value("debug_almost_zero_with", ("1.0", "big"), Some("oh no")));
+
+    expect_failure!(not_almost_zero, try_not_almost_zero, (tiny), value("not_almost_zero", ("1e-20", "tiny"), None));
+    expect_failure!(not_almost_zero, try_not_almost_zero, (tiny, "oh {}", "no"), value("not_almost_zero", ("1e-20", "tiny"), Some("oh no")));
+    expect_failure!(debug debug_not_almost_zero, (tiny), value("debug_not_almost_zero", ("1e-20", "tiny"), None));
+    expect_failure!(debug debug_not_almost_zero, (tiny, "oh {}", "no"), value("debug_not_almost_zero", ("1e-20", "tiny"), Some("oh no")));
+
+    expect_failure!(not_almost_zero_with, try_not_almost_zero_with, (tiny, 0.5), value("not_almost_zero_with", ("1e-20", "tiny"), None));
+    expect_failure!(not_almost_zero_with, try_not_almost_zero_with, (tiny, 0.5, "oh {}", "no"), value("not_almost_zero_with", ("1e-20", "tiny"), Some("oh no")));
+    expect_failure!(debug debug_not_almost_zero_with, (tiny, 0.5), value("debug_not_almost_zero_with", ("1e-20", "tiny"), None));
+    expect_failure!(debug debug_not_almost_zero_with, (tiny, 0.5, "oh {}", "no"), value("debug_not_almost_zero_with", ("1e-20", "tiny"), Some("oh no")));
+}
+
+#[test]
+fn classify() {
+    let expected = |which: &str, value_: (&str, &str), result: &str, msg| format!("{}\nresult: {}", value(which, value_, msg), result);
+
+    let nan = f32::NAN;
+    let result = "was NaN (bits: `0x7fc00000`)";
+    expect_failure!(finite, try_finite, (nan), expected("finite", ("NaN", "nan"), result, None));
+    expect_failure!(finite, try_finite, (nan, "oh {}", "no"), expected("finite", ("NaN", "nan"), result, Some("oh no")));
+    expect_failure!(debug debug_finite, (nan), expected("debug_finite", ("NaN", "nan"), result, None));
+    expect_failure!(debug debug_finite, (nan, "oh {}", "no"), expected("debug_finite", ("NaN", "nan"), result, Some("oh no")));
+
+    let state: Vec<f64> = (0..10).map(|i| i as f64).chain([-f64::NAN]).collect();
+    let (state_val, result) = ("[..., 7.0, 8.0, 9.0, NaN]", "element 10 was -NaN (bits: `0xfff8000000000000`)");
+    expect_failure!(not_nan, try_not_nan, (state[..]), expected("not_nan", (state_val, "state[..]"), result, None));
+    expect_failure!(not_nan, try_not_nan, (state[..], "oh {}", "no"), expected("not_nan", (state_val, "state[..]"), result, Some("oh no")));
+    expect_failure!(debug debug_not_nan, (state[..]), expected("debug_not_nan", (state_val, "state[..]"), result, None));
+    expect_failure!(debug debug_not_nan, (state[..], "oh {}", "no"), expected("debug_not_nan", (state_val, "state[..]"), result, Some("oh no")));
+
+    let tiny = f64::from_bits(1);
+    let result = "was subnormal (bits: `0x0000000000000001`)";
+    expect_failure!(normal, try_normal, (tiny), expected("normal", ("5e-324", "tiny"), result, None));
+    expect_failure!(normal, try_normal, (tiny, "oh {}", "no"), expected("normal", ("5e-324", "tiny"), result, Some("oh no")));
+    expect_failure!(debug debug_normal, (tiny), expected("debug_normal", ("5e-324", "tiny"), result, None));
+    expect_failure!(debug debug_normal, (tiny, "oh {}", "no"), expected("debug_normal", ("5e-324", "tiny"), result, Some("oh no")));
+
+    let zeros = [0.0f32, -0.0];
+    let result = "element 1 was negative zero (bits: `0x80000000`)";
+    expect_failure!(sign_positive, try_sign_positive, (zeros), expected("sign_positive", ("[0.0, -0.0]", "zeros"), result, None));
+    expect_failure!(
+        sign_positive,
+        try_sign_positive,
+        (zeros, "oh {}", "no"),
+        expected("sign_positive", ("[0.0, -0.0]", "zeros"), result, Some("oh no"))
+    );
+    expect_failure!(debug debug_sign_positive, (zeros), expected("debug_sign_positive", ("[0.0, -0.0]", "zeros"), result, None));
+    expect_failure!(
+        debug debug_sign_positive,
+        (zeros, "oh {}", "no"),
+        expected("debug_sign_positive", ("[0.0, -0.0]", "zeros"), result, Some("oh no"))
+    );
+
+    let inf = f64::INFINITY;
+    let result = "was +inf (bits: `0x7ff0000000000000`)";
+    expect_failure!(sign_negative, try_sign_negative, (inf), expected("sign_negative", ("inf", "inf"), result, None));
+    expect_failure!(sign_negative, try_sign_negative, (inf, "oh {}", "no"), expected("sign_negative", ("inf", "inf"), result, Some("oh no")));
+    expect_failure!(debug debug_sign_negative, (inf), expected("debug_sign_negative", ("inf", "inf"), result, None));
+    expect_failure!(debug debug_sign_negative, (inf, "oh {}", "no"), expected("debug_sign_negative", ("inf", "inf"), result, Some("oh no")));
+
+    chek::finite!(&state[..10]);
+    chek::normal!([1.0, f64::MIN_POSITIVE, -f64::MAX]);
+    chek::sign_positive!(zeros[0]);
+    chek::sign_negative!(-f32::NAN);
+    assert!(chek::try_normal!(0.0f32).is_err());
+}
+
+#[test]
+fn unwrap() {
+    let (some, none): (Option<i32>, Option<i32>) = (Some(3), None);
+    let (ok, err): (Result<i32, &str>, Result<i32, &str>) = (Ok(3), Err("bad"));
+
+    expect_failure!(some, try_some, (none), value("some", ("None", "none"), None));
+    expect_failure!(some, try_some, (none, "oh {}", "no"), value("some", ("None", "none"), Some("oh no")));
+    expect_failure!(debug debug_some, (none), value("debug_some", ("None", "none"), None));
+    expect_failure!(debug debug_some, (none, "oh {}", "no"), value("debug_some", ("None", "none"), Some("oh no")));
+
+    expect_failure!(none, try_none, (some), value("none", ("Some(3)", "some"), None));
+    expect_failure!(none, try_none, (some, "oh {}", "no"), value("none", ("Some(3)", "some"), Some("oh no")));
+    expect_failure!(debug debug_none, (some), value("debug_none", ("Some(3)", "some"), None));
+    expect_failure!(debug debug_none, (some, "oh {}", "no"), value("debug_none", ("Some(3)", "some"), Some("oh no")));
+
+    expect_failure!(ok, try_ok, (err), value("ok", ("Err(\"bad\")", "err"), None));
+    expect_failure!(ok, try_ok, (err, "oh {}", "no"), value("ok", ("Err(\"bad\")", "err"), Some("oh no")));
+    expect_failure!(debug debug_ok, (err), value("debug_ok", ("Err(\"bad\")", "err"), None));
+    expect_failure!(debug debug_ok, (err, "oh {}", "no"), value("debug_ok", ("Err(\"bad\")", "err"), Some("oh no")));
+
+    expect_failure!(err, try_err, (ok), value("err", ("Ok(3)", "ok"), None));
+    expect_failure!(err, try_err, (ok, "oh {}", "no"), value("err", ("Ok(3)", "ok"), Some("oh no")));
+    expect_failure!(debug debug_err, (ok), value("debug_err", ("Ok(3)", "ok"), None));
+    expect_failure!(debug debug_err, (ok, "oh {}", "no"), value("debug_err", ("Ok(3)", "ok"), Some("oh no")));
+
+    // Without debug assertions, these can't skip the check like the other
+    // `debug_` assertions, since they have no value to return.
+    if !cfg!(debug_assertions) {
+        let no_value = |which: &str| Some(format!("`chek::{}!` failed, and has no value to return", which));
+        assert_eq!(panic_message(|| { chek::debug_some!(none); }), no_value("debug_some"));
+        assert_eq!(panic_message(|| { chek::debug_ok!(err); }), no_value("debug_ok"));
+        assert_eq!(panic_message(|| { chek::debug_err!(ok); }), no_value("debug_err"));
+    }
+}
+
+#[test]
+fn matches() {
+    let v: Result<u8, &str> = Err("timed out");
+    let expected = |which: &str, pat: &str, msg: &str| {
+        format!("assertion failed: `chek::{}!(v, {})`{}\n value: `Err(\"timed out\")`", which, pat, msg)
+    };
+    let debug = |which: &str, pat: &str, msg: &str| if cfg!(debug_assertions) { Some(expected(which, pat, msg)) } else { None };
+
+    expect_failure!(matches, try_matches, (v, Ok(0..=9)), expected("matches", "Ok(0..=9)", ""));
+    expect_failure!(
+        matches,
+        try_matches,
+        (v, Ok(_) | Err("") if true, "oh {}", "no"),
+        expected("matches", "Ok(_) | Err(\"\") if true", ": oh no")
+    );
+    assert_eq!(panic_message(|| chek::debug_matches!(v, Ok(0..=9))), debug("debug_matches", "Ok(0..=9)", ""));
+    assert_eq!(
+        panic_message(|| chek::debug_matches!(v, Ok(x) if x > 3, "oh {}", "no")),
+        debug("debug_matches", "Ok(x) if x > 3", ": oh no"),
+    );
+
+    expect_failure!(matches, try_matches, (v, Ok(x) => x), expected("matches", "Ok(x)", ""));
+    expect_failure!(matches, try_matches, (v, Ok(x) if x > 3 => x, "oh {}", "no"), expected("matches", "Ok(x) if x > 3", ": oh no"));
+    assert_eq!(chek::matches!(v, Err(e) => e.len()), 9);
+    assert_eq!(chek::try_matches!(v, Err(e) => e.len()).ok(), Some(9));
+
+    expect_failure!(not_matches, try_not_matches, (v, Err(_)), expected("not_matches", "Err(_)", ""));
+    expect_failure!(
+        not_matches,
+        try_not_matches,
+        (v, Err(e) if e.len() > 3, "oh {}", "no"),
+        expected("not_matches", "Err(e) if e.len() > 3", ": oh no")
+    );
+    assert_eq!(panic_message(|| chek::debug_not_matches!(v, Err(_))), debug("debug_not_matches", "Err(_)", ""));
+    assert_eq!(
+        panic_message(|| chek::debug_not_matches!(v, Ok(_) | Err(_), "oh {}", "no")),
+        debug("debug_not_matches", "Ok(_) | Err(_)", ": oh no"),
+    );
+
+    let ensure = |v: Result<u8, &str>| -> Result<u8, Box<chek::Failure>> {
+        chek::ensure_not_matches!(v, Ok(0));
+        Ok(chek::ensure_matches!(v, Ok(x) => x))
+    };
+    assert_eq!(ensure(v).unwrap_err().to_string(), expected("matches", "Ok(x)", ""));
+    assert_eq!(ensure(Ok(0)).unwrap_err().to_string(), "assertion failed: `chek::not_matches!(v, Ok(0))`\n value: `Ok(0)`");
+    assert_eq!(ensure(Ok(3)).ok(), Some(3));
+    let ensure = |v: Result<u8, &str>| -> Result<(), String> {
+        chek::ensure_matches!(v, Ok(_), |failure: chek::Failure| failure.to_string());
+        Ok(())
+    };
+    assert_eq!(ensure(v), Err(expected("matches", "Ok(_)", "")));
+}
+
+#[test]
+fn check() {
+    let v: Vec<usize> = (1..=3).collect();
+    let n = 2;
+    let expected = |which: &str, msg: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(v.len() < n || !v.is_empty() && v[0] == n)`{}", which, msg),
+            "       v.len(): `3`",
+            "             n: `2`",
+            "  v.is_empty(): `false`",
+            "          v[0]: `1`",
+            "             n: `2`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(check, try_check, (v.len() < n || !v.is_empty() && v[0] == n), expected("check", ""));
+    expect_failure!(check, try_check, (v.len() < n || !v.is_empty() && v[0] == n, "oh {}", "no"), expected("check", ": oh no"));
+    let debug_expected = |msg| if cfg!(debug_assertions) { Some(expected("debug_check", msg)) } else { None };
+    assert_eq!(
+        panic_message(|| chek::debug_check!(v.len() < n || !v.is_empty() && v[0] == n)),
+        debug_expected(""),
+    );
+    assert_eq!(
+        panic_message(|| chek::debug_check!(v.len() < n || !v.is_empty() && v[0] == n, "oh {}", "no")),
+        debug_expected(": oh no"),
+    );
+    // Short-circuited operands are reported as such.
+    assert_eq!(
+        panic_message(|| chek::check!(v.is_empty() && v[0] == n)).unwrap(),
+        [
+            "assertion failed: `chek::check!(v.is_empty() && v[0] == n)`",
+            "  v.is_empty(): `false`",
+            "          v[0]: (not evaluated)",
+            "             n: (not evaluated)",
+        ]
+        .join("\n"),
+    );
+
+    // `ensure_check!` passes the expression on to `try_check!` intact.
+    let ensure = |v: &[usize]| -> Result<(), Box<chek::Failure>> {
+        chek::ensure_check!(v.is_empty() || v[0] == n);
+        Ok(())
+    };
+    let expected = "assertion failed: `chek::check!(v.is_empty() || v[0] == n)`\n             v: `[1, 2, 3]`\n  v.is_empty(): `false`\n          v[0]: `1`\n             n: `2`";
+    assert_eq!(ensure(&v).unwrap_err().to_string(), expected);
+    assert!(ensure(&[2]).is_ok());
+}
+
+#[test]
+fn check_calls() {
+    fn is_odd(x: usize) -> bool {
+        x % 2 == 1
+    }
+    let v: Vec<usize> = (1..=3).collect();
+    let (s, x, n) = ("ab", 4, 6);
+
+    // Receivers and arguments are recorded if they're `Copy` (like `s` and
+    // `x`, but not `v`), or references to a place (like `&x`).
+    let expected = |which: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(v.contains(&x))`", which),
+            "               x: `4`",
+            "  v.contains(&x): `false`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(check, try_check, (v.contains(&x)), expected("check"));
+    let expected = |which: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(s.len() + v.len() == n)`", which),
+            "                  s: `\"ab\"`",
+            "            s.len(): `2`",
+            "            v.len(): `3`",
+            "  s.len() + v.len(): `5`",
+            "                  n: `6`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(check, try_check, (s.len() + v.len() == n), expected("check"));
+    let expected = |which: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(is_odd(x * 2))`", which),
+            "              x: `4`",
+            "          x * 2: `8`",
+            "  is_odd(x * 2): `false`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(check, try_check, (is_odd(x * 2)), expected("check"));
+    let expected = |which: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(x.max(n) < 5)`", which),
+            "         x: `4`",
+            "         n: `6`",
+            "  x.max(n): `6`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(check, try_check, (x.max(n) < 5), expected("check"));
+
+    // Calls that mutate or consume their receiver still work.
+    let mut iter = v.iter();
+    chek::check!(iter.next() == Some(&1) && iter.len() == 2);
+    let name = Some(String::from("chek"));
+    chek::check!(name.unwrap() + "!" == "chek!");
+}
+
+#[test]
+fn ensure_error() {
+    enum MyError {
+        Check(Box<chek::Failure>),
+        Other(&'static str),
+    }
+    fn check(failure: chek::Failure) -> MyError {
+        MyError::Check(Box::new(failure))
+    }
+    fn run(x: i32, y: Option<i32>) -> Result<i32, MyError> {
+        chek::ensure_lt!(x, 10, check);
+        chek::ensure_ne!(x, 5, |failure| MyError::Check(Box::new(failure)));
+        chek::ensure_ne!(x, 3, MyError::Other("three"));
+        Ok(chek::ensure_some!(y, check))
+    }
+    let message = |result: Result<i32, MyError>| match result {
+        Err(MyError::Check(failure)) => failure.to_string(),
+        Err(MyError::Other(s)) => s.to_string(),
+        Ok(value) => value.to_string(),
+    };
+
+    // Errors that take a `chek::Failure` get the same one `try_` would return.
+    let (x, y) = (12, Some(1));
+    assert_eq!(message(run(x, y)), chek::try_lt!(x, 10).unwrap_err().to_string());
+    let x = 5;
+    assert_eq!(message(run(x, y)), chek::try_ne!(x, 5).unwrap_err().to_string());
+    let (x, y) = (1, None::<i32>);
+    assert_eq!(message(run(x, y)), chek::try_some!(y).unwrap_err().to_string());
+    // Others are returned as they are.
+    assert_eq!(message(run(3, Some(1))), "three");
+    assert_eq!(message(run(1, Some(2))), "2");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn failure_is_error() {
+    fn run(x: i32) -> Result<(), Box<dyn std::error::Error>> {
+        chek::ensure_eq!(x, 2);
+        Ok(())
+    }
+    let x = 1;
+    assert_eq!(run(x).unwrap_err().to_string(), chek::try_eq!(x, 2).unwrap_err().to_string());
+    assert!(run(2).is_ok());
+}
+
+#[test]
+fn ordered() {
+    let (start, cursor, end) = (0, 5, 5);
+    let expected = |which: &str, msg: &str| {
+        [
+            &format!("assertion failed: `chek::{}!(start <= cursor < end)`{}", which, msg),
+            "      start: `0`",
+            "     cursor: `5`",
+            "        end: `5`",
+            "  failed at: `cursor < end`",
+        ]
+        .join("\n")
+    };
+    expect_failure!(ordered, try_ordered, (start <= cursor < end), expected("ordered", ""));
+    expect_failure!(ordered, try_ordered, (start <= cursor < end, "oh {}", "no"), expected("ordered", ": oh no"));
+    let debug_expected = |msg| if cfg!(debug_assertions) { Some(expected("debug_ordered", msg)) } else { None };
+    assert_eq!(panic_message(|| chek::debug_ordered!(start <= cursor < end)), debug_expected(""));
+    assert_eq!(
+        panic_message(|| chek::debug_ordered!(start <= cursor < end, "oh {}", "no")),
+        debug_expected(": oh no"),
+    );
+    // Every operand is evaluated once, even after the chain has failed, and
+    // the first broken link is the one reported.
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    assert_eq!(
+        panic_message(std::panic::AssertUnwindSafe(|| chek::ordered!(next() > 3 >= next() == Vec::<u8>::new().len()))).unwrap(),
+        [
+            "assertion failed: `chek::ordered!(next() > 3 >= next() == Vec::<u8>::new().len())`",
+            "                  next(): `1`",
+            "                       3: `3`",
+            "                  next(): `2`",
+            "  Vec::<u8>::new().len(): `0`",
+            "               failed at: `next() > 3`",
+        ]
+        .join("\n"),
+    );
+    assert_eq!(calls, 2);
+
+    // `ensure_ordered!` takes an error rather than a message.
+    let ensure = |cursor: usize| -> Result<(), Box<chek::Failure>> {
+        chek::ensure_ordered!(start <= cursor < end);
+        Ok(())
+    };
+    assert_eq!(ensure(cursor).unwrap_err().to_string(), expected("ordered", ""));
+    assert!(ensure(3).is_ok());
+    let ensure = |cursor: usize| -> Result<(), String> {
+        chek::ensure_ordered!(start <= cursor < end, |failure: chek::Failure| failure.to_string(),);
+        Ok(())
+    };
+    assert_eq!(ensure(cursor), Err(expected("ordered", "")));
+}
+
+#[test]
+fn sorted() {
+    let v = [1.0, 2.0, 2.0, f64::NAN, 0.5];
+    let expected = |which: &str, items: &str, msg: &str, pair: ((&str, usize), (&str, usize))| {
+        format!(
+            "assertion failed: `chek::{}!({})`{}\n  left: `{}` (at index {}),\n right: `{}` (at index {})",
+            which,
+            items,
+            msg,
+            (pair.0).0,
+            (pair.0).1,
+            (pair.1).0,
+            (pair.1).1,
+        )
+    };
+    let debug = |which: &str, items: &str, msg: &str, pair| {
+        if cfg!(debug_assertions) { Some(expected(which, items, msg, pair)) } else { None }
+    };
+
+    let nan = (("2.0", 2), ("NaN", 3));
+    expect_failure!(sorted, try_sorted, (&v), expected("sorted", "&v", "", nan));
+    expect_failure!(sorted, try_sorted, (&v, "oh {}", "no"), expected("sorted", "&v", ": oh no", nan));
+    assert_eq!(panic_message(|| chek::debug_sorted!(&v)), debug("debug_sorted", "&v", "", nan));
+    assert_eq!(panic_message(|| chek::debug_sorted!(&v, "oh {}", "no")), debug("debug_sorted", "&v", ": oh no", nan));
+
+    let equal = (("2.0", 1), ("2.0", 2));
+    expect_failure!(strictly_sorted, try_strictly_sorted, (&v), expected("strictly_sorted", "&v", "", equal));
+    expect_failure!(
+        strictly_sorted,
+        try_strictly_sorted,
+        (&v, "oh {}", "no"),
+        expected("strictly_sorted", "&v", ": oh no", equal)
+    );
+    assert_eq!(panic_message(|| chek::debug_strictly_sorted!(&v)), debug("debug_strictly_sorted", "&v", "", equal));
+
+    // With a total order, NaN sorts after everything else.
+    let last = (("NaN", 3), ("0.5", 4));
+    // This takes the same `&f64`s as `slice::sort_by` would.
+    let by = f64::total_cmp;
+    expect_failure!(sorted_by, try_sorted_by, (&v, by), expected("sorted_by", "&v, by", "", last));
+    expect_failure!(sorted_by, try_sorted_by, (&v, by, "oh {}", "no"), expected("sorted_by", "&v, by", ": oh no", last));
+    assert_eq!(panic_message(|| chek::debug_sorted_by!(&v, by)), debug("debug_sorted_by", "&v, by", "", last));
+
+    let words = ["a", "bb", "cc", "d"];
+    let shorter = (("\"cc\"", 2), ("\"d\"", 3));
+    expect_failure!(
+        sorted_by_key,
+        try_sorted_by_key,
+        (words.iter(), |w| w.len()),
+        expected("sorted_by_key", "words.iter(), |w| w.len()", "", shorter)
+    );
+    expect_failure!(
+        sorted_by_key,
+        try_sorted_by_key,
+        (words.iter(), |w| w.len(), "oh {}", "no"),
+        expected("sorted_by_key", "words.iter(), |w| w.len()", ": oh no", shorter)
+    );
+    assert_eq!(
+        panic_message(|| chek::debug_sorted_by_key!(words.iter(), |w| w.len())),
+        debug("debug_sorted_by_key", "words.iter(), |w| w.len()", "", shorter),
+    );
+
+    // Empty and single-item inputs are trivially sorted.
+    chek::strictly_sorted!(&[] as &[u8]);
+    chek::strictly_sorted!(Some(1));
+
+    let ensure = |v: &[f64]| -> Result<(), Box<chek::Failure>> {
+        chek::ensure_sorted_by!(v, |a, b| a.total_cmp(b));
+        Ok(())
+    };
+    assert_eq!(ensure(&v).unwrap_err().to_string(), expected("sorted_by", "v, |a, b| a.total_cmp(b)", "", last));
+    assert!(ensure(&[0.5, f64::NAN]).is_ok());
+    let ensure = |words: &[&str]| -> Result<(), String> {
+        chek::ensure_sorted_by_key!(words, |w| w.len(), |failure: chek::Failure| failure.to_string());
+        Ok(())
+    };
+    assert_eq!(ensure(&words), Err(expected("sorted_by_key", "words, |w| w.len()", "", shorter)));
+}
+
+/// Runs this file again with `inline_panics` and `diff` toggled.
+#[test]
+fn other_mode() {
+    if std::env::var_os("CHEK_OUTPUT_TEST_NESTED").is_some() {
+        return;
+    }
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut cmd = Command::new(env!("CARGO"));
+    cmd.current_dir(manifest_dir)
+        .args(["test", "--quiet", "--test", "output", "--no-default-features"])
+        .arg("--target-dir")
+        .arg(manifest_dir.join("target").join("output-test"))
+        .env("CHEK_OUTPUT_TEST_NESTED", "1");
+    let features: Vec<&str> = [
+        ("inline_panics", cfg!(feature = "inline_panics")),
+        ("diff", cfg!(feature = "diff")),
+        ("std", cfg!(feature = "std")),
+    ]
+        .iter()
+        .filter(|(_, enabled)| !enabled)
+        .map(|(feature, _)| *feature)
+        .collect();
+    cmd.arg(format!("--features={}", features.join(",")));
+    let status = cmd.status().expect("failed to run cargo");
+    assert!(status.success(), "tests failed with inline_panics, diff and std toggled");
+}
@@ -0,0 +1,44 @@
+//! Compares the size of `examples/bloat.rs` when built with and without the
+//! `inline_panics` feature, so that changes to how much code the assertions
+//! generate get noticed.
+//!
+//! This builds the sample twice in release mode, so it's ignored by default.
+//! Run it with `cargo test --test size -- --ignored`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn build_sample(features: &[&str]) -> u64 {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let name = if features.is_empty() { "out-of-line" } else { "inline" };
+    let target_dir = manifest_dir.join("target").join("size-test").join(name);
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["build", "--quiet", "--release", "--example", "bloat", "--no-default-features"])
+        .arg(format!("--features={}", features.join(",")))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env("CARGO_PROFILE_RELEASE_STRIP", "symbols")
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "false")
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building the sample binary failed");
+    let exe: PathBuf = target_dir
+        .join("release")
+        .join("examples")
+        .join(format!("bloat{}", std::env::consts::EXE_SUFFIX));
+    std::fs::metadata(&exe).expect("sample binary is missing").len()
+}
+
+#[test]
+#[ignore = "builds the sample binary twice in release mode"]
+fn out_of_line_is_smaller() {
+    let inline = build_sample(&["inline_panics"]);
+    let out_of_line = build_sample(&[]);
+    assert!(
+        out_of_line < inline,
+        "out-of-line failures ({} bytes) should be smaller than inline ones ({} bytes)",
+        out_of_line,
+        inline,
+    );
+}